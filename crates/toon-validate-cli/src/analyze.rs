@@ -3,7 +3,9 @@ use prettytable::{row, Table};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
-use toon_validate_core::{InputFormat, Parser, TokenBreakdown, TokenEstimator};
+use toon_validate_core::{InputFormat, JsonLinesParser, Parser, TokenBreakdown, TokenEstimator};
+
+use crate::input::resolve_format;
 
 #[derive(Serialize, Deserialize)]
 pub struct AnalysisResult {
@@ -11,6 +13,16 @@ pub struct AnalysisResult {
     pub format: String,
     pub total_tokens: usize,
     pub breakdown: TokenBreakdownJson,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fold: Option<FoldSummary>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FoldSummary {
+    pub records: usize,
+    pub unfolded_tokens: usize,
+    pub folded_tokens: usize,
+    pub saved_tokens: usize,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -39,28 +51,44 @@ impl From<&TokenBreakdown> for TokenBreakdownJson {
 pub fn analyze_file(
     path: &Path,
     format: Option<InputFormat>,
+    fold: bool,
     json_output: bool,
 ) -> Result<()> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
     
-    let input_format = format.unwrap_or_else(|| Parser::detect_format(&content));
+    let input_format = resolve_format(path, &content, format);
     
-    let value = Parser::parse(&content, input_format)
+    let mut value = Parser::parse(&content, input_format)
         .with_context(|| format!("Failed to parse file: {}", path.display()))?;
     
+    // Folding turns a root array of uniform records (e.g. a JSON Lines log)
+    // into a table, showing what the same data would cost as TOON
+    let mut fold_summary = None;
+    if fold {
+        let unfolded_tokens = TokenEstimator::estimate_breakdown(&value).total();
+        value = JsonLinesParser::fold(value);
+        if let Some(table) = value.as_table() {
+            let folded_tokens = TokenEstimator::estimate_breakdown(&value).total();
+            fold_summary = Some(FoldSummary {
+                records: table.rows.len(),
+                unfolded_tokens,
+                folded_tokens,
+                saved_tokens: unfolded_tokens.saturating_sub(folded_tokens),
+            });
+        }
+    }
+    
     let breakdown = TokenEstimator::estimate_breakdown(&value);
     let total_tokens = breakdown.total();
     
     if json_output {
         let result = AnalysisResult {
             file: path.display().to_string(),
-            format: match input_format {
-                InputFormat::Toon => "toon".to_string(),
-                InputFormat::Json => "json".to_string(),
-            },
+            format: input_format.name().to_string(),
             total_tokens,
             breakdown: TokenBreakdownJson::from(&breakdown),
+            fold: fold_summary,
         };
         println!("{}", serde_json::to_string_pretty(&result)?);
     } else {
@@ -68,6 +96,24 @@ pub fn analyze_file(
         println!("Format: {:?}", input_format);
         println!("Total Estimated Tokens: {}", total_tokens);
         
+        if let Some(summary) = &fold_summary {
+            let percentage = if summary.unfolded_tokens > 0 {
+                (summary.saved_tokens as f64 / summary.unfolded_tokens as f64) * 100.0
+            } else {
+                0.0
+            };
+            println!(
+                "Folded {} records into a table: {} -> {} tokens ({} saved, {:.1}%)",
+                summary.records,
+                summary.unfolded_tokens,
+                summary.folded_tokens,
+                summary.saved_tokens,
+                percentage
+            );
+        } else if fold {
+            println!("Records are not uniform objects; analyzed without folding");
+        }
+        
         let mut table = Table::new();
         table.add_row(row!["Component", "Tokens", "Percentage"]);
         
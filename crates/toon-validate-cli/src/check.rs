@@ -2,96 +2,180 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
-use toon_validate_core::{InputFormat, Parser, TqError, Validator};
+use toon_validate_core::{
+    diagnostic::codes, Diagnostic, FixSafety, InputFormat, Parser, Schema, Severity, Suppressions,
+    TokenEstimator, TqError, Validator, apply_fixes, suggest_fixes,
+};
+use similar::TextDiff;
+
+use crate::commands::FixMode;
+use crate::input::resolve_format;
+
+/// Version of the `--json` output; bumped on incompatible changes.
+/// The format is documented in `docs/diagnostics.md`.
+pub const CHECK_OUTPUT_VERSION: u32 = 1;
+
+/// Exit code when the only errors are token budget overruns.
+pub const EXIT_OVER_BUDGET: i32 = 3;
 
 #[derive(Serialize, Deserialize)]
 pub struct CheckResult {
+    pub version: u32,
     pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
     pub valid: bool,
-    pub errors: Vec<String>,
-    pub warnings: Vec<String>,
+    pub summary: CheckSummary,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CheckSummary {
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
 }
 
 pub fn check_file(
     path: &Path,
     format: Option<InputFormat>,
+    schema_path: Option<&Path>,
+    validator: &Validator,
+    budget: Option<usize>,
+    fix_mode: FixMode,
     json_output: bool,
 ) -> Result<()> {
-    let content = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read file: {}", path.display()))?;
-    
-    let input_format = format.unwrap_or_else(|| Parser::detect_format(&content));
-    
-    let parse_result = Parser::parse(&content, input_format);
-    
-    let (value, parse_error) = match parse_result {
-        Ok(v) => (Some(v), None),
-        Err(e) => (None, Some(e)),
+    // Load the schema first so a broken schema is reported as such
+    let schema = match schema_path {
+        Some(schema_path) => {
+            let schema_content = fs::read_to_string(schema_path)
+                .with_context(|| format!("Failed to read schema: {}", schema_path.display()))?;
+            Some(Schema::parse(&schema_content)
+                .with_context(|| format!("Failed to load schema: {}", schema_path.display()))?)
+        }
+        None => None,
     };
     
-    let mut errors = Vec::new();
-    let mut warnings = Vec::new();
+    let mut content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
     
-    if let Some(e) = parse_error {
-        errors.push(format!("Parse error: {}", e));
-        
-        if json_output {
-            let result = CheckResult {
-                file: path.display().to_string(),
-                valid: false,
-                errors,
-                warnings,
-            };
-            println!("{}", serde_json::to_string_pretty(&result)?);
-        } else {
-            println!("\nValidation Result: {}", path.display());
-            println!("Status: INVALID");
-            println!("Errors:");
-            for error in &errors {
-                println!("  - {}", error);
+    let input_format = resolve_format(path, &content, format);
+    
+    // Files that fail to parse have nothing to fix and are reported below
+    if let FixMode::Apply(max_safety) | FixMode::DryRun(max_safety) = fix_mode {
+        if let Ok(diagnostics) = diagnose(&content, input_format, schema.as_ref(), validator, budget) {
+            let outcome = apply_fixes(&content, &diagnostics, max_safety);
+            
+            if let FixMode::DryRun(_) = fix_mode {
+                if outcome.applied == 0 {
+                    println!("No fixes available for {}", path.display());
+                } else {
+                    let name = path.display().to_string();
+                    print!("{}", TextDiff::from_lines(&content, &outcome.text)
+                        .unified_diff()
+                        .header(&format!("a/{}", name), &format!("b/{}", name)));
+                }
+            } else if outcome.applied > 0 {
+                fs::write(path, &outcome.text)
+                    .with_context(|| format!("Failed to write file: {}", path.display()))?;
+                eprintln!("Applied {} fix(es) to {}", outcome.applied, path.display());
             }
+            // A dry run reports what would remain after the fixes
+            content = outcome.text;
         }
-        
-        // Parse error is exit code 1, validation error is exit code 2
-        let exit_code = match &e {
-            TqError::TableRowMismatch { .. } | 
-            TqError::TableSchemaInconsistent { .. } | 
-            TqError::Validation(_) => 2,
-            _ => 1,
-        };
-        std::process::exit(exit_code);
     }
     
-    let value = value.unwrap();
-    
-    // Perform validation
-    match Validator::validate(&value) {
-        Ok(_) => {}
+    let diagnostics = match diagnose(&content, input_format, schema.as_ref(), validator, budget) {
+        Ok(diagnostics) => diagnostics,
         Err(e) => {
-            match &e {
+            report(path, input_format, &e.to_diagnostics(), json_output)?;
+            
+            // Parse error is exit code 1, validation error is exit code 2
+            let exit_code = match &e {
                 TqError::TableRowMismatch { .. } | 
-                TqError::TableSchemaInconsistent { .. } => {
-                    errors.push(format!("Validation error: {}", e));
-                }
-                _ => {
-                    errors.push(format!("Error: {}", e));
-                }
-            }
+                TqError::TableSchemaInconsistent { .. } | 
+                TqError::TableColumnTypeMismatch { .. } | 
+                TqError::Validation(_) => 2,
+                _ => 1,
+            };
+            std::process::exit(exit_code);
         }
+    };
+    
+    let is_valid = report(path, input_format, &diagnostics, json_output)?;
+    
+    if !is_valid {
+        // Being over budget alone gets its own exit code
+        let only_budget = diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .all(|d| d.code == codes::TOKEN_BUDGET_EXCEEDED);
+        std::process::exit(if only_budget { EXIT_OVER_BUDGET } else { 2 });
     }
     
-    // Check structure for warnings
-    let structural_issues = Validator::check_structure(&value);
-    warnings.extend(structural_issues);
+    Ok(())
+}
+
+/// Parses and validates the content, returning every diagnostic that isn't
+/// suppressed, errors first.
+fn diagnose(
+    content: &str,
+    input_format: InputFormat,
+    schema: Option<&Schema>,
+    validator: &Validator,
+    budget: Option<usize>,
+) -> std::result::Result<Vec<Diagnostic>, TqError> {
+    let (value, source_map) = Parser::parse_with_source_map(content, input_format)?;
+    
+    let mut diagnostics = validator.check(&value);
+    Validator::locate_surplus_rows(&mut diagnostics, &value, &source_map);
+    diagnostics.extend(validator.rules().run_source(&source_map));
+    
+    if let Some(schema) = schema {
+        diagnostics.extend(schema.validate(&value).into_iter().map(|violation| {
+            Diagnostic::error(codes::SCHEMA_VIOLATION, &violation.path, violation.message)
+        }));
+    }
+    
+    if let Some(overrun) = budget.and_then(|budget| TokenEstimator::check_budget(&value, budget)) {
+        diagnostics.push(Diagnostic::error(codes::TOKEN_BUDGET_EXCEEDED, "", overrun.message()));
+    }
     
-    let is_valid = errors.is_empty();
+    source_map.attach(&mut diagnostics);
+    if input_format == InputFormat::Toon {
+        suggest_fixes(&mut diagnostics, &value, content, &source_map);
+        validator.rules().suggest_fixes(&mut diagnostics, &value, content, &source_map);
+    }
+    let mut diagnostics = Suppressions::from_comments(source_map.comments()).apply(diagnostics);
+    
+    // Errors first, keeping the validator's order within each severity
+    diagnostics.sort_by_key(|d| std::cmp::Reverse(d.severity));
+    Ok(diagnostics)
+}
+
+/// Prints the diagnostics and returns whether the file is valid.
+fn report(
+    path: &Path,
+    input_format: InputFormat,
+    diagnostics: &[Diagnostic],
+    json_output: bool,
+) -> Result<bool> {
+    let count = |severity: Severity| diagnostics.iter().filter(|d| d.severity == severity).count();
+    let summary = CheckSummary {
+        errors: count(Severity::Error),
+        warnings: count(Severity::Warning),
+        infos: count(Severity::Info),
+    };
+    let is_valid = summary.errors == 0;
     
     if json_output {
         let result = CheckResult {
+            version: CHECK_OUTPUT_VERSION,
             file: path.display().to_string(),
+            format: Some(input_format.name().to_string()),
             valid: is_valid,
-            errors,
-            warnings,
+            summary,
+            diagnostics: diagnostics.to_vec(),
         };
         println!("{}", serde_json::to_string_pretty(&result)?);
     } else {
@@ -99,28 +183,44 @@ pub fn check_file(
         println!("Format: {:?}", input_format);
         println!("Status: {}", if is_valid { "VALID" } else { "INVALID" });
         
-        if !errors.is_empty() {
-            println!("\nErrors:");
-            for error in &errors {
-                println!("  - {}", error);
+        for (severity, heading) in [
+            (Severity::Error, "Errors"),
+            (Severity::Warning, "Warnings"),
+            (Severity::Info, "Notes"),
+        ] {
+            let matching: Vec<&Diagnostic> = diagnostics.iter().filter(|d| d.severity == severity).collect();
+            if matching.is_empty() {
+                continue;
             }
-        }
-        
-        if !warnings.is_empty() {
-            println!("\nWarnings:");
-            for warning in &warnings {
-                println!("  - {}", warning);
+            println!("\n{}:", heading);
+            for diagnostic in matching {
+                let fixable = match &diagnostic.fix {
+                    Some(fix) if fix.safety == FixSafety::Safe => " (fixable)",
+                    Some(_) => " (suggested fix)",
+                    None => "",
+                };
+                match diagnostic.span {
+                    Some(span) => println!("  - line {}: {}{}", span.line, diagnostic, fixable),
+                    None => println!("  - {}{}", diagnostic, fixable),
+                }
             }
         }
         
-        if is_valid && errors.is_empty() && warnings.is_empty() {
+        if diagnostics.is_empty() {
             println!("\nNo issues found.");
         }
+        
+        let fixes = |safety: FixSafety| {
+            diagnostics.iter().filter(|d| d.fix.as_ref().is_some_and(|fix| fix.safety == safety)).count()
+        };
+        let (fixable, suggested) = (fixes(FixSafety::Safe), fixes(FixSafety::Suggested));
+        if fixable > 0 {
+            println!("\n{} issue(s) can be fixed with --fix (preview with --fix-dry-run).", fixable);
+        }
+        if suggested > 0 {
+            println!("{} issue(s) have suggested fixes that change the data; apply them with --fix --fix-suggested.", suggested);
+        }
     }
     
-    if !is_valid {
-        std::process::exit(2);
-    }
-    
-    Ok(())
-}
\ No newline at end of file
+    Ok(is_valid)
+}
@@ -1,5 +1,6 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
+use toon_validate_core::{FixSafety, RuleOverride};
 
 #[derive(Parser)]
 #[command(name = "tq")]
@@ -16,10 +17,14 @@ pub enum Commands {
         /// Path to the file to analyze
         path: PathBuf,
         
-        /// Input format (toon or json)
+        /// Input format (auto, toon, json, json5, jsonl, csv, tsv or toml)
         #[arg(long = "in", value_enum)]
         format: Option<Format>,
         
+        /// Fold a root array of uniform records into a table before analyzing
+        #[arg(long)]
+        fold: bool,
+        
         /// Output in JSON format
         #[arg(long)]
         json: bool,
@@ -34,7 +39,7 @@ pub enum Commands {
         #[arg(long = "ext")]
         extensions: Vec<String>,
         
-        /// Input format (toon or json)
+        /// Input format (auto, toon, json, json5, jsonl, csv, tsv or toml)
         #[arg(long = "in", value_enum)]
         format: Option<Format>,
         
@@ -48,27 +53,185 @@ pub enum Commands {
         /// Path to the file to check
         path: PathBuf,
         
-        /// Input format (toon or json)
+        /// Input format (auto, toon, json, json5, jsonl, csv, tsv or toml)
         #[arg(long = "in", value_enum)]
         format: Option<Format>,
         
+        /// JSON Schema the document must conform to
+        #[arg(long)]
+        schema: Option<PathBuf>,
+        
+        /// Set a lint rule's level, e.g. `empty-array=off` (off, info, warn or error)
+        #[arg(long = "rule", value_name = "ID=LEVEL")]
+        rules: Vec<RuleOverride>,
+        
+        /// Apply safe fixes and write them back to the file
+        #[arg(long, group = "fix_mode", conflicts_with = "fix_dry_run")]
+        fix: bool,
+        
+        /// Print the fixes as a unified diff without changing the file
+        #[arg(long, group = "fix_mode")]
+        fix_dry_run: bool,
+        
+        /// With --fix or --fix-dry-run, also apply suggested fixes, which change
+        /// values or key names (quoting ambiguous numbers, renaming keys)
+        #[arg(long, requires = "fix_mode")]
+        fix_suggested: bool,
+        
+        /// Validation profile: strict also fails on empty tables and duplicate keys,
+        /// lenient allows rows with missing columns
+        #[arg(long, value_enum, default_value_t = Profile::Default)]
+        profile: Profile,
+        
+        /// Fail when the estimated token count exceeds N (overrides config budgets)
+        #[arg(long, value_name = "N")]
+        max_tokens: Option<usize>,
+        
         /// Output in JSON format
         #[arg(long)]
         json: bool,
     },
+    
+    /// Infer a JSON Schema from sample documents
+    InferSchema {
+        /// Files or directories of sample documents
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        
+        /// File extensions to read from directories (can be specified multiple times)
+        #[arg(long = "ext")]
+        extensions: Vec<String>,
+        
+        /// Input format (auto, toon, json, json5, jsonl, csv, tsv or toml)
+        #[arg(long = "in", value_enum)]
+        format: Option<Format>,
+        
+        /// Strings with at most this many distinct values become an enum (0 disables enums)
+        #[arg(long, default_value_t = 8)]
+        max_enum: usize,
+        
+        /// Write the schema to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    
+    /// Compare two JSON Schemas and report breaking changes
+    SchemaDiff {
+        /// The old schema (or sample documents with --infer)
+        old: PathBuf,
+        
+        /// The new schema (or sample documents with --infer)
+        new: PathBuf,
+        
+        /// Infer both schemas from sample files or directories
+        #[arg(long)]
+        infer: bool,
+        
+        /// File extensions to read from directories with --infer (can be specified multiple times)
+        #[arg(long = "ext", requires = "infer")]
+        extensions: Vec<String>,
+        
+        /// Input format of the samples with --infer
+        #[arg(long = "in", value_enum, requires = "infer")]
+        format: Option<Format>,
+        
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Inspect the `.tval.toml` project configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    
+    /// Export a table from a file as CSV or TSV
+    ExportTable {
+        /// Path to the file containing the table
+        path: PathBuf,
+        
+        /// Path of the table within the document (e.g. `users` or `data.orders`)
+        table: String,
+        
+        /// Input format (auto, toon, json, json5, jsonl, csv, tsv or toml)
+        #[arg(long = "in", value_enum)]
+        format: Option<Format>,
+        
+        /// Write comma-separated output (default)
+        #[arg(long, conflicts_with = "tsv")]
+        csv: bool,
+        
+        /// Write tab-separated output
+        #[arg(long)]
+        tsv: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the effective configuration, with defaults filled in
+    Show {
+        /// File or directory to find the configuration for
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// What `check` does with fixes, and the riskiest fixes it may apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixMode {
+    Off,
+    Apply(FixSafety),
+    DryRun(FixSafety),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Profile {
+    Strict,
+    Default,
+    Lenient,
+}
+
+impl Profile {
+    pub fn to_validation_profile(self) -> toon_validate_core::ValidationProfile {
+        match self {
+            Profile::Strict => toon_validate_core::ValidationProfile::Strict,
+            Profile::Default => toon_validate_core::ValidationProfile::Default,
+            Profile::Lenient => toon_validate_core::ValidationProfile::Lenient,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum Format {
+    /// Detect from the file extension and content
+    Auto,
     Toon,
     Json,
+    Json5,
+    Jsonl,
+    Csv,
+    Tsv,
+    Toml,
 }
 
 impl Format {
-    pub fn to_input_format(self) -> toon_validate_core::InputFormat {
+    /// Returns `None` for `auto`, leaving the choice to format detection
+    pub fn to_input_format(self) -> Option<toon_validate_core::InputFormat> {
         match self {
-            Format::Toon => toon_validate_core::InputFormat::Toon,
-            Format::Json => toon_validate_core::InputFormat::Json,
+            Format::Auto => None,
+            Format::Toon => Some(toon_validate_core::InputFormat::Toon),
+            Format::Json => Some(toon_validate_core::InputFormat::Json),
+            Format::Json5 => Some(toon_validate_core::InputFormat::Json5),
+            Format::Jsonl => Some(toon_validate_core::InputFormat::JsonLines),
+            Format::Csv => Some(toon_validate_core::InputFormat::Csv),
+            Format::Tsv => Some(toon_validate_core::InputFormat::Tsv),
+            Format::Toml => Some(toon_validate_core::InputFormat::Toml),
         }
     }
 }
\ No newline at end of file
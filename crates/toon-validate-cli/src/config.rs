@@ -0,0 +1,279 @@
+use anyhow::{Context, Result};
+use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toon_validate_core::{rules::RuleOptions, InputFormat, RuleLevel, RuleRegistry};
+
+pub const CONFIG_FILE_NAME: &str = ".tval.toml";
+
+/// Extensions `tval profile` scans when neither `--ext` nor the config sets them
+pub const DEFAULT_PROFILE_EXTENSIONS: [&str; 2] = ["toon", "json"];
+
+/// The contents of a `.tval.toml` file.
+///
+/// Glob keys are matched against paths relative to the directory holding the
+/// file; when several globs match, the first one in the file wins.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub output: OutputConfig,
+    /// Input format per glob (`auto`, `toon`, `json`, ...)
+    pub formats: IndexMap<String, String>,
+    pub profile: ProfileConfig,
+    /// Lint rule levels, optionally with rule options
+    pub rules: IndexMap<String, RuleSetting>,
+    /// JSON Schema per glob, relative to the config file
+    pub schemas: IndexMap<String, PathBuf>,
+    /// Maximum estimated tokens per glob
+    pub budgets: IndexMap<String, usize>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    pub json: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProfileConfig {
+    pub extensions: Vec<String>,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+/// Either a bare level (`empty-array = "off"`) or a table with a level and
+/// rule options (`naming = { level = "error", style = "snake" }`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RuleSetting {
+    Level(RuleLevel),
+    Detailed {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        level: Option<RuleLevel>,
+        #[serde(flatten)]
+        options: RuleOptions,
+    },
+}
+
+/// A loaded config with its globs compiled.
+pub struct ProjectConfig {
+    /// The `.tval.toml` the config came from; `None` when using defaults
+    pub path: Option<PathBuf>,
+    pub config: Config,
+    root: PathBuf,
+    formats: Vec<(GlobMatcher, Option<InputFormat>)>,
+    schemas: Vec<(GlobMatcher, PathBuf)>,
+    budgets: Vec<(GlobMatcher, usize)>,
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl ProjectConfig {
+    /// Looks for `.tval.toml` in the target's directory and its ancestors,
+    /// falling back to the defaults when there is none.
+    pub fn discover(target: &Path) -> Result<Self> {
+        let target = absolute(target);
+        let start = if target.is_file() {
+            target.parent().unwrap_or(&target)
+        } else {
+            target.as_path()
+        };
+    
+        for dir in start.ancestors() {
+            let candidate = dir.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                return Self::load(&candidate);
+            }
+        }
+    
+        let cwd = std::env::current_dir().unwrap_or_default();
+        Self::from_config(Config::default(), None, cwd)
+    }
+    
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config: {}", path.display()))?;
+        let config: Config = toml::from_str(&content)
+            .with_context(|| format!("Failed to load config: {}", path.display()))?;
+        let root = absolute(path).parent().map(Path::to_path_buf).unwrap_or_default();
+        Self::from_config(config, Some(path.to_path_buf()), root)
+            .with_context(|| format!("Invalid config: {}", path.display()))
+    }
+    
+    fn from_config(config: Config, path: Option<PathBuf>, root: PathBuf) -> Result<Self> {
+        let mut formats = Vec::new();
+        for (pattern, format) in &config.formats {
+            let format = if format.eq_ignore_ascii_case("auto") {
+                None
+            } else {
+                Some(format.parse::<InputFormat>()?)
+            };
+            formats.push((compile(pattern)?, format));
+        }
+    
+        let mut schemas = Vec::new();
+        for (pattern, schema) in &config.schemas {
+            schemas.push((compile(pattern)?, root.join(schema)));
+        }
+    
+        let mut budgets = Vec::new();
+        for (pattern, budget) in &config.budgets {
+            budgets.push((compile(pattern)?, *budget));
+        }
+    
+        let include = if config.profile.include.is_empty() {
+            None
+        } else {
+            Some(compile_set(&config.profile.include)?)
+        };
+        let exclude = compile_set(&config.profile.exclude)?;
+    
+        // Catch unknown rules and bad options when loading rather than on first use
+        let project = ProjectConfig { path, config, root, formats, schemas, budgets, include, exclude };
+        project.rule_registry()?;
+        Ok(project)
+    }
+    
+    /// The configured input format for a file; `None` means detect it.
+    pub fn format_for(&self, path: &Path) -> Option<InputFormat> {
+        let relative = self.relative(path);
+        self.formats
+            .iter()
+            .find(|(glob, _)| glob.is_match(&relative))
+            .and_then(|(_, format)| *format)
+    }
+    
+    pub fn schema_for(&self, path: &Path) -> Option<PathBuf> {
+        let relative = self.relative(path);
+        self.schemas
+            .iter()
+            .find(|(glob, _)| glob.is_match(&relative))
+            .map(|(_, schema)| schema.clone())
+    }
+    
+    /// The token budget for a file, which `tval check` enforces.
+    pub fn budget_for(&self, path: &Path) -> Option<usize> {
+        let relative = self.relative(path);
+        self.budgets
+            .iter()
+            .find(|(glob, _)| glob.is_match(&relative))
+            .map(|(_, budget)| *budget)
+    }
+    
+    /// Whether `tval profile` should look at a file, per `include`/`exclude`.
+    pub fn is_profiled(&self, path: &Path) -> bool {
+        let relative = self.relative(path);
+        let included = self.include.as_ref().is_none_or(|set| set.is_match(&relative));
+        included && !self.exclude.is_match(&relative)
+    }
+    
+    /// Extensions for `tval profile`, without leading dots.
+    pub fn profile_extensions(&self) -> Vec<String> {
+        if self.config.profile.extensions.is_empty() {
+            DEFAULT_PROFILE_EXTENSIONS.iter().map(|e| e.to_string()).collect()
+        } else {
+            self.config
+                .profile
+                .extensions
+                .iter()
+                .map(|e| e.trim_start_matches('.').to_string())
+                .collect()
+        }
+    }
+    
+    /// The default rules with the configured levels and options applied.
+    pub fn rule_registry(&self) -> Result<RuleRegistry> {
+        let mut registry = RuleRegistry::with_defaults();
+        self.configure_rules(&mut registry)?;
+        Ok(registry)
+    }
+    
+    /// Applies the configured levels and options to `registry`, overriding
+    /// any levels it already has.
+    pub fn configure_rules(&self, registry: &mut RuleRegistry) -> Result<()> {
+        for (id, setting) in &self.config.rules {
+            match setting {
+                RuleSetting::Level(level) => registry.set_level(id, *level)?,
+                RuleSetting::Detailed { level, options } => {
+                    registry.configure(id, options)?;
+                    if let Some(level) = level {
+                        registry.set_level(id, *level)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// The config with defaults filled in, as used by the commands.
+    pub fn effective(&self) -> Config {
+        let mut config = self.config.clone();
+        config.profile.extensions = self.profile_extensions();
+    
+        let registry = self.rule_registry().unwrap_or_else(|_| RuleRegistry::with_defaults());
+        let mut rules = IndexMap::new();
+        for (rule, level) in registry.rules() {
+            let setting = match config.rules.get(rule.id()) {
+                Some(RuleSetting::Detailed { options, .. }) if !options.is_empty() => {
+                    RuleSetting::Detailed { level: Some(level), options: options.clone() }
+                }
+                _ => RuleSetting::Level(level),
+            };
+            rules.insert(rule.id().to_string(), setting);
+        }
+        config.rules = rules;
+        config
+    }
+    
+    fn relative(&self, path: &Path) -> PathBuf {
+        let path = absolute(path);
+        path.strip_prefix(&self.root).map(Path::to_path_buf).unwrap_or(path)
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    }
+}
+
+fn compile(pattern: &str) -> Result<GlobMatcher> {
+    Ok(Glob::new(pattern)
+        .with_context(|| format!("Invalid glob: {}", pattern))?
+        .compile_matcher())
+}
+
+fn compile_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).with_context(|| format!("Invalid glob: {}", pattern))?);
+    }
+    Ok(builder.build()?)
+}
+
+/// Prints the effective config for `tval config show`.
+pub fn show_config(target: &Path, json_output: bool) -> Result<()> {
+    let project = ProjectConfig::discover(target)?;
+    let effective = project.effective();
+    
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&effective)?);
+        return Ok(());
+    }
+    
+    match &project.path {
+        Some(path) => println!("# Loaded from {}", path.display()),
+        None => println!("# No {} found; showing defaults", CONFIG_FILE_NAME),
+    }
+    print!("{}", toml::to_string_pretty(&effective).context("Failed to render config")?);
+    Ok(())
+}
+
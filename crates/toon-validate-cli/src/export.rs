@@ -0,0 +1,33 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+use toon_validate_core::{CsvWriter, InputFormat, Parser};
+
+use crate::input::resolve_format;
+
+pub fn export_table(
+    path: &Path,
+    table_path: &str,
+    format: Option<InputFormat>,
+    delimiter: u8,
+) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    
+    let input_format = resolve_format(path, &content, format);
+    
+    let value = Parser::parse(&content, input_format)
+        .with_context(|| format!("Failed to parse file: {}", path.display()))?;
+    
+    let table = match value.get_path(table_path) {
+        Some(found) => match found.as_table() {
+            Some(table) => table,
+            None => bail!("Value at '{}' is not a table", table_path),
+        },
+        None => bail!("No value found at '{}'", table_path),
+    };
+    
+    print!("{}", CsvWriter::write_table(table, delimiter)?);
+    
+    Ok(())
+}
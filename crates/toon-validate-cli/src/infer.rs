@@ -0,0 +1,90 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toon_validate_core::{InferenceOptions, InputFormat, Parser, SchemaInferrer};
+use walkdir::WalkDir;
+
+use crate::config::ProjectConfig;
+use crate::input::resolve_format;
+
+/// Infers a JSON Schema from the given files and the matching files in the
+/// given directories, and prints it or writes it to `output`.
+pub fn infer_schema(
+    paths: &[PathBuf],
+    extensions: Vec<String>,
+    format: Option<InputFormat>,
+    config: &ProjectConfig,
+    options: InferenceOptions,
+    output: Option<&Path>,
+) -> Result<()> {
+    let inferrer = infer_from(paths, &extensions, format, config, options)?;
+    
+    let schema = serde_json::to_string_pretty(&inferrer.to_json_schema())?;
+    match output {
+        Some(output) => {
+            fs::write(output, format!("{}\n", schema))
+                .with_context(|| format!("Failed to write schema: {}", output.display()))?;
+            eprintln!("Inferred schema from {} document(s) written to {}", inferrer.samples(), output.display());
+        }
+        None => println!("{}", schema),
+    }
+    
+    Ok(())
+}
+
+/// Feeds the given files and the matching files in the given directories to
+/// a new inferrer. Fails when no document could be read.
+pub fn infer_from(
+    paths: &[PathBuf],
+    extensions: &[String],
+    format: Option<InputFormat>,
+    config: &ProjectConfig,
+    options: InferenceOptions,
+) -> Result<SchemaInferrer> {
+    let mut inferrer = SchemaInferrer::new(options);
+    
+    for path in paths {
+        if path.is_dir() {
+            for entry in WalkDir::new(path)
+                .follow_links(true)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(|e| e.ok())
+            {
+                let file = entry.path();
+                let matches_extension = file.extension().is_some_and(|ext| {
+                    extensions.iter().any(|e| e.trim_start_matches('.') == ext.to_string_lossy())
+                });
+                if !entry.file_type().is_file() || !matches_extension || !config.is_profiled(file) {
+                    continue;
+                }
+                
+                // Directories may hold stray files; skip what doesn't parse
+                if let Err(e) = add_file(&mut inferrer, file, format.or_else(|| config.format_for(file))) {
+                    eprintln!("Warning: Skipping {}: {:#}", file.display(), e);
+                }
+            }
+        } else {
+            add_file(&mut inferrer, path, format.or_else(|| config.format_for(path)))?;
+        }
+    }
+    
+    if inferrer.samples() == 0 {
+        bail!("No documents found to infer a schema from");
+    }
+    
+    Ok(inferrer)
+}
+
+fn add_file(inferrer: &mut SchemaInferrer, path: &Path, format: Option<InputFormat>) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    
+    let input_format = resolve_format(path, &content, format);
+    
+    let value = Parser::parse(&content, input_format)
+        .with_context(|| format!("Failed to parse file: {}", path.display()))?;
+    
+    inferrer.add(&value);
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use std::path::Path;
+use toon_validate_core::{Confidence, InputFormat, Parser};
+
+/// Uses the `--in` format when given, otherwise detects the format from the
+/// file extension and content, warning when the two disagree.
+pub fn resolve_format(path: &Path, content: &str, format: Option<InputFormat>) -> InputFormat {
+    if let Some(format) = format {
+        return format;
+    }
+    
+    let extension = path.extension().map(|ext| ext.to_string_lossy());
+    let detection = Parser::detect(content, extension.as_deref());
+    
+    if detection.is_conflict() {
+        eprintln!(
+            "Warning: {} has a .{} extension but its content looks like {}; parsing as {} ({:?} confidence)",
+            path.display(),
+            extension.as_deref().unwrap_or_default(),
+            detection.format.name(),
+            detection.format.name(),
+            detection.confidence
+        );
+    } else if detection.confidence == Confidence::Low && detection.extension_format.is_none() {
+        eprintln!(
+            "Warning: could not confidently detect the format of {}; assuming {}",
+            path.display(),
+            detection.format.name()
+        );
+    }
+    
+    detection.format
+}
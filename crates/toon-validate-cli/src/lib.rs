@@ -5,12 +5,19 @@
 mod analyze;
 mod check;
 mod commands;
+mod config;
+mod export;
+mod infer;
+mod input;
 mod profile;
+mod schema_diff;
 
 use anyhow::Result;
 use clap::Parser;
-use commands::{Cli, Commands};
+use commands::{Cli, Commands, ConfigAction, FixMode};
+use config::ProjectConfig;
 use std::process;
+use toon_validate_core::{FixSafety, InferenceOptions, RuleRegistry, Validator, ValidatorOptions};
 
 /// Main entry point for the CLI
 pub fn main() {
@@ -24,9 +31,16 @@ fn run() -> Result<()> {
     let cli = Cli::parse();
     
     match cli.command {
-        Commands::Analyze { path, format, json } => {
-            let input_format = format.map(|f| f.to_input_format());
-            analyze::analyze_file(&path, input_format, json)?;
+        Commands::Analyze {
+            path,
+            format,
+            fold,
+            json,
+        } => {
+            let config = ProjectConfig::discover(&path)?;
+            let input_format = format
+                .map_or_else(|| config.format_for(&path), |f| f.to_input_format());
+            analyze::analyze_file(&path, input_format, fold, json || config.config.output.json)?;
         }
         Commands::Profile {
             dir,
@@ -34,12 +48,110 @@ fn run() -> Result<()> {
             format,
             json,
         } => {
-            let input_format = format.map(|f| f.to_input_format());
-            profile::profile_directory(&dir, extensions, input_format, json)?;
+            let config = ProjectConfig::discover(&dir)?;
+            let input_format = format.and_then(|f| f.to_input_format());
+            let extensions = if extensions.is_empty() {
+                config.profile_extensions()
+            } else {
+                extensions
+            };
+            let json = json || config.config.output.json;
+            profile::profile_directory(&dir, extensions, input_format, &config, json)?;
+        }
+        Commands::Check {
+            path,
+            format,
+            schema,
+            rules: rule_overrides,
+            fix,
+            fix_dry_run,
+            fix_suggested,
+            profile,
+            max_tokens,
+            json,
+        } => {
+            let config = ProjectConfig::discover(&path)?;
+            let input_format = format
+                .map_or_else(|| config.format_for(&path), |f| f.to_input_format());
+            let schema = schema.or_else(|| config.schema_for(&path));
+            // The profile sets the rule levels, the config overrides them,
+            // and --rule overrides both
+            let mut options = ValidatorOptions::for_profile(profile.to_validation_profile());
+            let mut rules = RuleRegistry::with_defaults();
+            options.apply_rule_levels(&mut rules)?;
+            config.configure_rules(&mut rules)?;
+            options.rule_levels = rule_overrides;
+            let validator = Validator::builder().options(options).rules(rules).build()?;
+            let fix_safety = if fix_suggested { FixSafety::Suggested } else { FixSafety::Safe };
+            let fix_mode = match (fix, fix_dry_run) {
+                (true, _) => FixMode::Apply(fix_safety),
+                (_, true) => FixMode::DryRun(fix_safety),
+                _ => FixMode::Off,
+            };
+            let budget = max_tokens.or_else(|| config.budget_for(&path));
+            let json = json || config.config.output.json;
+            check::check_file(&path, input_format, schema.as_deref(), &validator, budget, fix_mode, json)?;
         }
-        Commands::Check { path, format, json } => {
-            let input_format = format.map(|f| f.to_input_format());
-            check::check_file(&path, input_format, json)?;
+        Commands::InferSchema {
+            paths,
+            extensions,
+            format,
+            max_enum,
+            output,
+        } => {
+            let config = ProjectConfig::discover(&paths[0])?;
+            let input_format = format.and_then(|f| f.to_input_format());
+            let extensions = if extensions.is_empty() {
+                config.profile_extensions()
+            } else {
+                extensions
+            };
+            let options = InferenceOptions {
+                max_enum_values: max_enum,
+                ..InferenceOptions::default()
+            };
+            infer::infer_schema(&paths, extensions, input_format, &config, options, output.as_deref())?;
+        }
+        Commands::SchemaDiff {
+            old,
+            new,
+            infer,
+            extensions,
+            format,
+            json,
+        } => {
+            let config = ProjectConfig::discover(&old)?;
+            let extensions = if extensions.is_empty() {
+                config.profile_extensions()
+            } else {
+                extensions
+            };
+            let source = if infer {
+                schema_diff::SchemaSource::Samples {
+                    extensions: &extensions,
+                    format: format.and_then(|f| f.to_input_format()),
+                    config: &config,
+                }
+            } else {
+                schema_diff::SchemaSource::Files
+            };
+            schema_diff::schema_diff(&old, &new, &source, json)?;
+        }
+        Commands::Config { action } => match action {
+            ConfigAction::Show { path, json } => config::show_config(&path, json)?,
+        }
+        Commands::ExportTable {
+            path,
+            table,
+            format,
+            csv: _,
+            tsv,
+        } => {
+            let config = ProjectConfig::discover(&path)?;
+            let input_format = format
+                .map_or_else(|| config.format_for(&path), |f| f.to_input_format());
+            let delimiter = if tsv { b'\t' } else { b',' };
+            export::export_table(&path, &table, input_format, delimiter)?;
         }
     }
     
@@ -1,11 +1,15 @@
 use anyhow::{Context, Result};
 use prettytable::{row, Table};
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 use std::fs;
 use std::path::Path;
 use toon_validate_core::{InputFormat, Parser, TokenEstimator};
 use walkdir::WalkDir;
 
+use crate::config::ProjectConfig;
+use crate::input::resolve_format;
+
 #[derive(Serialize, Deserialize)]
 pub struct ProfileResult {
     pub directory: String,
@@ -25,17 +29,12 @@ pub fn profile_directory(
     dir: &Path,
     extensions: Vec<String>,
     format: Option<InputFormat>,
+    config: &ProjectConfig,
     json_output: bool,
 ) -> Result<()> {
     let mut files = Vec::new();
     let mut total_tokens = 0;
     
-    let extensions: Vec<String> = if extensions.is_empty() {
-        vec!["toon".to_string(), "json".to_string()]
-    } else {
-        extensions
-    };
-    
     for entry in WalkDir::new(dir)
         .follow_links(true)
         .into_iter()
@@ -47,16 +46,16 @@ pub fn profile_directory(
         
         let path = entry.path();
         let should_process = if let Some(ext) = path.extension() {
-            extensions.iter().any(|e| e == &ext.to_string_lossy())
+            extensions.iter().any(|e| e.trim_start_matches('.') == ext.to_string_lossy())
         } else {
             false
         };
         
-        if !should_process {
+        if !should_process || !config.is_profiled(path) {
             continue;
         }
         
-        match process_file(path, format) {
+        match process_file(path, format.or_else(|| config.format_for(path))) {
             Ok(profile) => {
                 total_tokens += profile.tokens;
                 files.push(profile);
@@ -68,7 +67,7 @@ pub fn profile_directory(
     }
     
     // Sort by token count descending
-    files.sort_by(|a, b| b.tokens.cmp(&a.tokens));
+    files.sort_by_key(|f| Reverse(f.tokens));
     
     if json_output {
         let result = ProfileResult {
@@ -125,7 +124,7 @@ fn process_file(path: &Path, format: Option<InputFormat>) -> Result<FileProfile>
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
     
-    let input_format = format.unwrap_or_else(|| Parser::detect_format(&content));
+    let input_format = resolve_format(path, &content, format);
     
     let value = Parser::parse(&content, input_format)
         .with_context(|| format!("Failed to parse file: {}", path.display()))?;
@@ -135,9 +134,6 @@ fn process_file(path: &Path, format: Option<InputFormat>) -> Result<FileProfile>
     Ok(FileProfile {
         path: path.display().to_string(),
         tokens,
-        format: match input_format {
-            InputFormat::Toon => "toon".to_string(),
-            InputFormat::Json => "json".to_string(),
-        },
+        format: input_format.name().to_string(),
     })
 }
\ No newline at end of file
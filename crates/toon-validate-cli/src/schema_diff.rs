@@ -0,0 +1,86 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toon_validate_core::{diff_schemas, ChangeKind, InferenceOptions, InputFormat, Schema, SchemaChange};
+
+use crate::config::ProjectConfig;
+use crate::infer::infer_from;
+
+#[derive(Serialize)]
+struct SchemaDiffResult<'a> {
+    old: String,
+    new: String,
+    breaking: usize,
+    non_breaking: usize,
+    changes: &'a [SchemaChange],
+}
+
+/// Where the schemas to compare come from.
+pub enum SchemaSource<'a> {
+    /// JSON Schema files
+    Files,
+    /// Sample documents, or directories of them, to infer each schema from
+    Samples {
+        extensions: &'a [String],
+        format: Option<InputFormat>,
+        config: &'a ProjectConfig,
+    },
+}
+
+/// Compares the schema at `old` with the one at `new` and exits with code 2
+/// when any change is breaking.
+pub fn schema_diff(old: &Path, new: &Path, source: &SchemaSource, json_output: bool) -> Result<()> {
+    let changes = diff_schemas(&load(old, source)?, &load(new, source)?);
+    let breaking = changes.iter().filter(|change| change.kind == ChangeKind::Breaking).count();
+    
+    if json_output {
+        let result = SchemaDiffResult {
+            old: old.display().to_string(),
+            new: new.display().to_string(),
+            breaking,
+            non_breaking: changes.len() - breaking,
+            changes: &changes,
+        };
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if changes.is_empty() {
+        println!("✓ No schema changes");
+    } else {
+        let (breaking_changes, other_changes): (Vec<_>, Vec<_>) =
+            changes.iter().partition(|change| change.kind == ChangeKind::Breaking);
+        for (heading, list) in [("Breaking changes", breaking_changes), ("Non-breaking changes", other_changes)] {
+            if list.is_empty() {
+                continue;
+            }
+            println!("{}:", heading);
+            for change in list {
+                println!("  {}", change);
+            }
+            println!();
+        }
+        println!("{} breaking, {} non-breaking change(s)", breaking, changes.len() - breaking);
+    }
+    
+    if breaking > 0 {
+        std::process::exit(2);
+    }
+    Ok(())
+}
+
+fn load(path: &Path, source: &SchemaSource) -> Result<Schema> {
+    match source {
+        SchemaSource::Files => {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read schema: {}", path.display()))?;
+            Schema::parse(&content).with_context(|| format!("Failed to load schema: {}", path.display()))
+        }
+        SchemaSource::Samples { extensions, format, config } => {
+            // Enums inferred from samples only list the values seen, so
+            // comparing them would report every new value as a change
+            let options = InferenceOptions { max_enum_values: 0, ..InferenceOptions::default() };
+            let inferrer = infer_from(&[PathBuf::from(path)], extensions, *format, config, options)
+                .with_context(|| format!("Failed to infer a schema from {}", path.display()))?;
+            Ok(Schema::from_json(&inferrer.to_json_schema())?)
+        }
+    }
+}
@@ -107,4 +107,963 @@ fn test_help_command() {
         .stdout(predicate::str::contains("analyze"))
         .stdout(predicate::str::contains("profile"))
         .stdout(predicate::str::contains("check"));
-}
\ No newline at end of file
+}
+#[test]
+fn test_analyze_csv_input() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("data.csv");
+    fs::write(&file_path, "id,name\n1,Alice\n2,Bob\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("analyze")
+        .arg(&file_path)
+        .arg("--in")
+        .arg("csv")
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"format\": \"csv\""))
+        .stdout(predicate::str::contains("\"table_rows\": 2"));
+}
+
+#[test]
+fn test_export_table_command() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("users.toon");
+    fs::write(&file_path, r#"users[2]:
+  - id: 1
+    name: "Alice"
+  - id: 2
+    name: "Bob""#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("export-table")
+        .arg(&file_path)
+        .arg("users")
+        .arg("--csv")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("id,name\n1,Alice\n2,Bob\n"));
+}
+
+#[test]
+fn test_analyze_jsonl_with_fold() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("log.jsonl");
+    fs::write(&file_path, "{\"id\": 1, \"msg\": \"a\"}\n{\"id\": 2, \"msg\": \"b\"}\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("analyze")
+        .arg(&file_path)
+        .arg("--fold")
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"format\": \"jsonl\""))
+        .stdout(predicate::str::contains("\"records\": 2"))
+        .stdout(predicate::str::contains("\"saved_tokens\""));
+}
+
+#[test]
+fn test_check_jsonl_reports_each_bad_line() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("log.jsonl");
+    fs::write(&file_path, "{\"id\": 1}\n{\"id\":\n{\"id\": 3}\n{oops}\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("Parse error at line 2"))
+        .stdout(predicate::str::contains("Parse error at line 4"));
+}
+
+#[test]
+fn test_analyze_jsonc_fixture_with_comments() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("fixture.json");
+    fs::write(&file_path, "// prompt fixture\n{\n  name: 'test', // inline\n  tags: [1, 2,],\n}\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("analyze")
+        .arg(&file_path)
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"format\": \"json5\""));
+}
+
+#[test]
+fn test_check_json5_reports_syntax_position() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("broken.json5");
+    fs::write(&file_path, "{\n  a: 1,\n  b 2\n}\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("line 3, column 5"));
+}
+
+#[test]
+fn test_profile_toml_files() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("tools.toml"), r#"[[tools]]
+name = "search"
+
+[[tools]]
+name = "fetch"
+"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("profile")
+        .arg(dir.path())
+        .arg("--ext")
+        .arg("toml")
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"total_files\": 1"))
+        .stdout(predicate::str::contains("\"format\": \"toml\""));
+}
+
+#[test]
+fn test_auto_format_detects_toon_root_table() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("rows.toon");
+    fs::write(&file_path, "[2]:\n  - id: 1\n  - id: 2\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("analyze")
+        .arg(&file_path)
+        .arg("--in")
+        .arg("auto")
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"format\": \"toon\""));
+}
+
+#[test]
+fn test_warns_when_extension_and_content_disagree() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("data.json");
+    fs::write(&file_path, "name: \"test\"\nage: 42\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("analyze")
+        .arg(&file_path)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Warning"))
+        .stderr(predicate::str::contains("looks like toon"));
+}
+
+#[test]
+fn test_check_with_schema_reports_all_violations() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("output.toon");
+    fs::write(&file_path, r#"status: "done"
+users[2]:
+  - id: 1
+    name: "Alice"
+  - id: "two"
+    name: "Bob""#).unwrap();
+    let schema_path = dir.path().join("schema.json");
+    fs::write(&schema_path, r#"{
+  "type": "object",
+  "required": ["status", "count"],
+  "properties": {
+    "status": {"enum": ["ok", "error"]},
+    "users": {"type": "array", "items": {"properties": {"id": {"type": "integer"}}}}
+  }
+}"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--schema")
+        .arg(&schema_path)
+        .assert()
+        .failure()
+        .code(2)
+        .stdout(predicate::str::contains("missing required property 'count'"))
+        .stdout(predicate::str::contains("status: value \"done\" is not one of"))
+        .stdout(predicate::str::contains("users[1].id: expected integer, found string"));
+}
+
+#[test]
+fn test_check_column_type_mismatch() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("mixed.toon");
+    fs::write(&file_path, r#"users[3]:
+  - id: 1
+  - id: "abc"
+  - id: 3"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .failure()
+        .code(2)
+        .stdout(predicate::str::contains("column id row 2: expected number, found string"));
+}
+
+#[test]
+fn test_check_lists_every_diagnostic_in_json() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("many.toon");
+    fs::write(&file_path, r#"tags: []
+users[3]:
+  - id: 1
+    name: "Alice"
+  - id: "two"
+    name: "Bob"
+  - id: 3"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    let output = cmd.arg("check")
+        .arg(&file_path)
+        .arg("--json")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let codes: Vec<&str> = result["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(codes, vec!["TV0202", "TV0203", "TV0302"]);
+    assert_eq!(result["diagnostics"][0]["severity"], "error");
+    assert_eq!(result["diagnostics"][0]["path"], "users[2]");
+    assert_eq!(result["diagnostics"][2]["severity"], "warning");
+}
+
+
+#[test]
+fn test_check_json_output_is_versioned_with_spans() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("spans.toon");
+    fs::write(&file_path, r#"users[2]:
+  - id: 1
+    name: "Alice"
+  - id: "two"
+    name: "Bob""#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    let output = cmd.arg("check")
+        .arg(&file_path)
+        .arg("--json")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["version"], 1);
+    assert_eq!(result["format"], "toon");
+    assert_eq!(result["summary"]["errors"], 1);
+    let diagnostic = &result["diagnostics"][0];
+    assert_eq!(diagnostic["code"], "TV0203");
+    assert_eq!(diagnostic["path"], "users[1].id");
+    assert_eq!(diagnostic["span"]["line"], 4);
+}
+
+#[test]
+fn test_check_rule_levels_can_be_overridden() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("empty.toon");
+    fs::write(&file_path, "tags: []\nname: \"x\"").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--rule")
+        .arg("empty-array=error")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("[TV0302] tags: Empty array"));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--rule")
+        .arg("empty-array=off")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No issues found."));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--rule")
+        .arg("no-such-rule=warn")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("unknown rule 'no-such-rule'"));
+}
+
+#[test]
+fn test_project_config_is_discovered_and_overridden_by_flags() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".tval.toml"), r#"
+[formats]
+"*.rec" = "jsonl"
+
+[rules]
+empty-array = "error"
+"#).unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    let records = dir.path().join("data").join("items.rec");
+    fs::write(&records, "{\"tags\": []}\n{\"tags\": [1]}\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&records)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Format: JsonLines"))
+        .stdout(predicate::str::contains("[TV0302] [0].tags: Empty array"));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&records)
+        .arg("--rule")
+        .arg("empty-array=warn")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_config_show_prints_effective_config() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".tval.toml"), "[rules]\nempty-object = \"off\"\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    let output = cmd.arg("config")
+        .arg("show")
+        .arg(dir.path())
+        .arg("--json")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    
+    let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(config["rules"]["empty-object"], "off");
+    assert_eq!(config["rules"]["empty-array"], "warn");
+    assert_eq!(config["profile"]["extensions"], serde_json::json!(["toon", "json"]));
+}
+
+#[test]
+fn test_invalid_project_config_is_reported() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(".tval.toml"), "[rules]\nno-such-rule = \"warn\"\n").unwrap();
+    let file_path = dir.path().join("a.toon");
+    fs::write(&file_path, "name: \"x\"").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Invalid config"))
+        .stderr(predicate::str::contains("unknown rule 'no-such-rule'"));
+}
+
+#[test]
+fn test_check_honors_suppression_comments() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("fixture.toon");
+    fs::write(&file_path, r#"# tval-disable-next-line empty-array
+tags: []
+users[2]:
+  - id: 1
+    name: "Alice"
+  # tval-disable-next-line table-schema
+  - id: 2
+# tval-disable-next-line empty-object
+name: "x""#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Status: VALID"))
+        .stdout(predicate::str::contains("line 8: [TV0501] Unused suppression for empty-object"))
+        .stdout(predicate::str::contains("Empty array").not());
+}
+
+#[test]
+fn test_check_fix_dry_run_and_fix() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("broken.toon");
+    let original = "users[3]:\n  - id: 1\n    name: \"Alice\"\n  - id: 2\n";
+    fs::write(&file_path, original).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--fix-dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("-users[3]:"))
+        .stdout(predicate::str::contains("+users[2]:"))
+        .stdout(predicate::str::contains("+    name: null"));
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), original);
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--fix")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Applied 2 fix(es)"))
+        .stdout(predicate::str::contains("Status: VALID"));
+    assert_eq!(
+        fs::read_to_string(&file_path).unwrap(),
+        "users[2]:\n  - id: 1\n    name: \"Alice\"\n  - id: 2\n    name: null\n"
+    );
+    
+    // Errors the fixes leave behind still fail a dry run
+    fs::write(&file_path, "users[3]:\n  - id: 1\n  - id: 2\n    extra: true\n").unwrap();
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--fix-dry-run")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("+users[2]:"))
+        .stdout(predicate::str::contains("Status: INVALID"));
+}
+
+#[test]
+fn test_infer_schema_from_directory_is_usable_by_check() {
+    let dir = tempdir().unwrap();
+    let samples = dir.path().join("samples");
+    fs::create_dir(&samples).unwrap();
+    fs::write(samples.join("a.toon"), r#"users[2]:
+  - id: 1
+    role: "admin"
+  - id: 2
+    role: "user""#).unwrap();
+    fs::write(samples.join("b.json"), r#"{"users": [{"id": 3, "role": "user", "email": "c@example.com"}, {"id": 4, "role": "admin"}]}"#).unwrap();
+    let schema_path = dir.path().join("schema.json");
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("infer-schema")
+        .arg(&samples)
+        .arg("-o")
+        .arg(&schema_path)
+        .assert()
+        .success();
+    
+    let schema: serde_json::Value = serde_json::from_str(&fs::read_to_string(&schema_path).unwrap()).unwrap();
+    let row = &schema["properties"]["users"]["items"];
+    assert_eq!(row["properties"]["id"]["type"], "integer");
+    assert_eq!(row["properties"]["role"]["enum"], serde_json::json!(["admin", "user"]));
+    assert_eq!(row["required"], serde_json::json!(["id", "role"]));
+    
+    let bad = dir.path().join("bad.toon");
+    fs::write(&bad, "users[1]:\n  - id: 1\n    role: \"root\"").unwrap();
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&bad)
+        .arg("--schema")
+        .arg(&schema_path)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("users[0].role"));
+}
+
+#[test]
+fn test_check_reports_duplicate_keys() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("orders.toon");
+    fs::write(&file_path, r#"orders[3]:
+  - id: 1
+    sku: "a"
+    line: 1
+  - id: 1
+    sku: "b"
+    line: 1
+  - id: 2
+    sku: "a"
+    line: 1"#).unwrap();
+
+    // `id` is checked automatically and only warns
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[TV0304] orders[1]: Duplicate id 1 in rows 1 and 2"));
+    
+    fs::write(dir.path().join(".tval.toml"), r#"
+[rules]
+unique-keys = { level = "error", keys = { orders = [["sku", "line"]] } }
+"#).unwrap();
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("orders[2]: Duplicate (sku, line) (\"a\", 1) in rows 1 and 3"));
+}
+
+#[test]
+fn test_check_reports_dangling_references() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("shop.toon");
+    fs::write(&file_path, r#"users[2]:
+  - id: 1
+  - id: 2
+orders[2]:
+  - id: 10
+    user_id: 2
+  - id: 11
+    user_id: 5"#).unwrap();
+    fs::write(dir.path().join(".tval.toml"), r#"
+[rules]
+foreign-keys = { references = { "orders.user_id" = "users.id" } }
+"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains(
+            "[TV0305] orders[1].user_id: Dangling reference: user_id 5 in orders row 2 matches no users.id",
+        ));
+}
+
+#[test]
+fn test_check_enforces_token_budget() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("prompt.toon");
+    fs::write(&file_path, r#"title: "A fairly long title for a prompt fragment"
+users[2]:
+  - id: 1
+    name: "Alice"
+  - id: 2
+    name: "Bob""#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--max-tokens")
+        .arg("10")
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains("[TV0601] Estimated"))
+        .stdout(predicate::str::contains("exceeds the budget of 10 by"))
+        .stdout(predicate::str::contains("by 19 (190%); largest top-level keys: users 16, title 13"));
+    
+    // A config budget applies per glob, and the flag overrides it
+    fs::write(dir.path().join(".tval.toml"), "[budgets]\n\"*.toon\" = 5\n").unwrap();
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains("exceeds the budget of 5 by"));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--max-tokens")
+        .arg("1000")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_check_quotes_ambiguous_scalars() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("product.toon");
+    fs::write(&file_path, "code: 0012\nversion: 1.10\nstock: 5\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[TV0306] code: Unquoted 0012 is read as the number 12"))
+        .stdout(predicate::str::contains("2 issue(s) have suggested fixes that change the data; apply them with --fix --fix-suggested."));
+    
+    // --fix-suggested alone would silently do nothing
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--fix-suggested")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("<--fix|--fix-dry-run>"));
+    
+    // Quoting changes the value, so plain --fix leaves it alone
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--fix-dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No fixes available"));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--fix")
+        .arg("--fix-suggested")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No issues found."));
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "code: \"0012\"\nversion: \"1.10\"\nstock: 5\n");
+}
+
+#[test]
+fn test_check_locates_table_rows() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("users.toon");
+    fs::write(&file_path, r#"users[3]:
+  - id: 1
+    name: "Alice"
+  - id: 2
+    name: "Bob"
+  - id: 3"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("line 6: [TV0202] users[2]: Inconsistent table schema in users: Row 3"));
+    
+    // A row field that isn't `key: value` is an error rather than skipped
+    fs::write(&file_path, r#"users[2]:
+  - id: 1
+    name "Alice"
+  - id: 2"#).unwrap();
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("line 3: [TV0101]"))
+        .stdout(predicate::str::contains("Table users row 1: expected 'key: value', found: name \"Alice\""));
+}
+
+#[test]
+fn test_check_reports_surplus_rows() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("users.toon");
+    fs::write(&file_path, r#"users[1]:
+  - id: 1
+  - id: 2
+name: "x""#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains(
+            "[TV0201] users: Table users declared with 1 rows but found 2; extra rows at line 3",
+        ));
+}
+
+#[test]
+fn test_check_profiles() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("users.toon");
+    fs::write(&file_path, r#"users[2]:
+  - id: 1
+    name: "Alice"
+  - id: 1"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--profile")
+        .arg("lenient")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TV0202").not());
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--profile")
+        .arg("strict")
+        .arg("--json")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("\"code\": \"TV0304\",\n      \"severity\": \"error\""));
+}
+
+#[test]
+fn test_check_config_overrides_profile() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("data.toon");
+    fs::write(&file_path, "tags: []
+name: \"a\"
+name: \"b\"
+").unwrap();
+    fs::write(dir.path().join(".tval.toml"), "[rules]\nempty-array = \"error\"\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--profile")
+        .arg("lenient")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("[TV0302] tags: Empty array"));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--profile")
+        .arg("strict")
+        .arg("--rule")
+        .arg("empty-array=off")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("line 3: [TV0308] name: Key name repeats line 2; only the last value is kept"))
+        .stdout(predicate::str::contains("TV0302").not());
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--profile")
+        .arg("strict")
+        .arg("--rule")
+        .arg("empty-array=off")
+        .arg("--rule")
+        .arg("repeated-key=off")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TV0308").not());
+}
+
+#[test]
+fn test_check_strict_forbids_empty_tables() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("users.toon");
+    fs::write(&file_path, "users[0]:\n").unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[TV0303] users: Empty table"));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--profile")
+        .arg("strict")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Status: INVALID"))
+        .stdout(predicate::str::contains("[TV0303] users: Empty table"));
+}
+
+#[test]
+fn test_schema_diff_classifies_changes() {
+    let dir = tempdir().unwrap();
+    let old = dir.path().join("old.json");
+    let new = dir.path().join("new.json");
+    fs::write(&old, r#"{"properties": {"id": {"type": "integer"}, "email": {"type": "string"}}, "required": ["id", "email"]}"#).unwrap();
+    fs::write(&new, r#"{"properties": {"id": {"type": "number"}, "nick": {"type": "string"}}, "required": ["id"]}"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("schema-diff")
+        .arg(&old)
+        .arg(&new)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Breaking changes:\n  email: required field removed"))
+        .stdout(predicate::str::contains("Non-breaking changes:\n  id: type widened from integer to number\n  nick: new optional field"));
+    
+    // Only additions are backwards compatible
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("schema-diff")
+        .arg(&new)
+        .arg(&new)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No schema changes"));
+    
+    let before = dir.path().join("before");
+    let after = dir.path().join("after");
+    fs::create_dir(&before).unwrap();
+    fs::create_dir(&after).unwrap();
+    fs::write(before.join("a.toon"), "id: 1\nname: Ada").unwrap();
+    fs::write(after.join("a.toon"), "id: 1\nname: Ada\nactive: true").unwrap();
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("schema-diff")
+        .arg("--infer")
+        .arg(&before)
+        .arg(&after)
+        .arg("--json")
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("\"kind\": \"breaking\",\n      \"path\": \"active\",\n      \"message\": \"new required field\""));
+}
+
+#[test]
+fn test_check_reports_string_format_outliers() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("events.toon");
+    fs::write(&file_path, r#"events[3]:
+  - id: 1
+    created_at: "2024-01-05T10:00:00Z"
+  - id: 2
+    created_at: "yesterday"
+  - id: 3
+    created_at: "2024-01-06T08:15:00+01:00""#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[TV0204] events[1].created_at: Table events column created_at row 2: expected a date-time string like the rest of the column, found \"yesterday\""));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--profile")
+        .arg("lenient")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TV0204").not());
+}
+
+#[test]
+fn test_check_renames_keys_to_naming_case() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("users.toon");
+    fs::write(&file_path, "userList[2]:\n  - userId: 1\n  - userId: 2\n").unwrap();
+    fs::write(dir.path().join(".tval.toml"), r#"
+[rules]
+naming = { case = "snake" }
+"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[TV0307] userList: Table userList is not snake_case (user_list)"))
+        .stdout(predicate::str::contains("[TV0307] userList[0].userId: Column userId is not snake_case (user_id)"))
+        .stdout(predicate::str::contains("2 issue(s) have suggested fixes"));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--fix")
+        .arg("--fix-suggested")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No issues found."));
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "user_list[2]:\n  - user_id: 1\n  - user_id: 2\n");
+}
+
+#[test]
+fn test_check_notes_token_waste() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("orders.toon");
+    fs::write(&file_path, r#"orders[3]:
+  - id: 1
+    currency: "EUR"
+    coupon: null
+  - id: 2
+    currency: "EUR"
+    coupon: null
+  - id: 3
+    currency: "EUR"
+    coupon: null"#).unwrap();
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Notes:"))
+        .stdout(predicate::str::contains("[TV0602] orders[0].currency: Column currency is \"EUR\" in all 3 rows of orders; stating it once outside the table would save ~"))
+        .stdout(predicate::str::contains("[TV0603] orders[0].coupon: Column coupon is empty in 3 of 3 rows of orders; dropping the column would save ~"));
+    
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--bin", "tval", "--"]);
+    cmd.arg("check")
+        .arg(&file_path)
+        .arg("--rule")
+        .arg("constant-column=error")
+        .assert()
+        .code(2);
+}
@@ -0,0 +1,150 @@
+use crate::{error::{Result, TqError}, value::{Table, Value}};
+use std::collections::HashMap;
+
+pub struct CsvParser;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColumnType {
+    Null,
+    Bool,
+    Number,
+    String,
+}
+
+impl CsvParser {
+    pub const TABLE_NAME: &'static str = "rows";
+    
+    pub fn parse(input: &str, delimiter: u8) -> Result<Value> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(input.as_bytes());
+        
+        let headers: Vec<String> = reader.headers()?.iter().map(|h| h.trim().to_string()).collect();
+        // Rows become maps, so a repeated header would silently drop cells
+        for (idx, header) in headers.iter().enumerate() {
+            if let Some(first) = headers[..idx].iter().position(|h| h == header) {
+                return Err(TqError::Parse {
+                    line: 1,
+                    message: format!("duplicate header '{}' in columns {} and {}", header, first + 1, idx + 1),
+                });
+            }
+        }
+        let mut records = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            records.push(record.iter().map(String::from).collect::<Vec<_>>());
+        }
+        
+        // Infer one type per column so that e.g. "0012" stays a string when
+        // the rest of the column is not numeric
+        let column_types: Vec<ColumnType> = (0..headers.len())
+            .map(|col| Self::infer_column_type(records.iter().map(|r| r[col].as_str())))
+            .collect();
+        
+        let rows: Vec<HashMap<String, Value>> = records
+            .iter()
+            .map(|record| {
+                headers
+                    .iter()
+                    .zip(&column_types)
+                    .zip(record)
+                    .map(|((header, ty), cell)| (header.clone(), Self::convert_cell(cell, *ty)))
+                    .collect()
+            })
+            .collect();
+        
+        Ok(Value::Table(Table {
+            name: Self::TABLE_NAME.to_string(),
+            declared_rows: rows.len(),
+            rows,
+        }))
+    }
+    
+    fn infer_column_type<'a>(cells: impl Iterator<Item = &'a str>) -> ColumnType {
+        let mut column_type = ColumnType::Null;
+        
+        for cell in cells.map(str::trim).filter(|c| !c.is_empty()) {
+            let cell_type = if cell == "true" || cell == "false" {
+                ColumnType::Bool
+            } else if cell.parse::<f64>().is_ok_and(f64::is_finite) {
+                ColumnType::Number
+            } else {
+                ColumnType::String
+            };
+            
+            column_type = match (column_type, cell_type) {
+                (ColumnType::Null, t) => t,
+                (a, b) if a == b => a,
+                _ => return ColumnType::String,
+            };
+        }
+        
+        column_type
+    }
+    
+    fn convert_cell(cell: &str, column_type: ColumnType) -> Value {
+        let trimmed = cell.trim();
+        if trimmed.is_empty() {
+            return Value::Null;
+        }
+        
+        match column_type {
+            ColumnType::Bool => Value::Bool(trimmed == "true"),
+            ColumnType::Number => Value::Number(trimmed.parse().unwrap_or(0.0)),
+            ColumnType::Null | ColumnType::String => Value::String(cell.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_with_type_inference() {
+        let input = "id,name,active,score\n1,Alice,true,9.5\n2,Bob,false,\n";
+        let result = CsvParser::parse(input, b',').unwrap();
+        
+        let table = result.as_table().expect("Expected table");
+        assert_eq!(table.name, "rows");
+        assert_eq!(table.declared_rows, 2);
+        assert_eq!(table.rows[0].get("id"), Some(&Value::Number(1.0)));
+        assert_eq!(table.rows[0].get("name"), Some(&Value::String("Alice".to_string())));
+        assert_eq!(table.rows[0].get("active"), Some(&Value::Bool(true)));
+        assert_eq!(table.rows[0].get("score"), Some(&Value::Number(9.5)));
+        assert_eq!(table.rows[1].get("score"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_mixed_column_stays_string() {
+        let input = "code\n0012\nABC\n";
+        let result = CsvParser::parse(input, b',').unwrap();
+        
+        let table = result.as_table().unwrap();
+        assert_eq!(table.rows[0].get("code"), Some(&Value::String("0012".to_string())));
+        assert_eq!(table.rows[1].get("code"), Some(&Value::String("ABC".to_string())));
+    }
+
+    #[test]
+    fn test_parse_tsv_with_quoted_fields() {
+        let input = "name\tnote\n\"Smith, J\"\t\"said \"\"hi\"\"\"\n";
+        let result = CsvParser::parse(input, b'\t').unwrap();
+        
+        let table = result.as_table().unwrap();
+        assert_eq!(table.rows[0].get("name"), Some(&Value::String("Smith, J".to_string())));
+        assert_eq!(table.rows[0].get("note"), Some(&Value::String("said \"hi\"".to_string())));
+    }
+
+    #[test]
+    fn test_duplicate_headers_are_an_error() {
+        let input = "id,name,id\n1,Alice,2\n";
+        let err = CsvParser::parse(input, b',').unwrap_err();
+        assert!(err.to_string().contains("duplicate header 'id' in columns 1 and 3"));
+    }
+
+    #[test]
+    fn test_ragged_rows_are_an_error() {
+        let input = "a,b\n1,2\n3\n";
+        assert!(CsvParser::parse(input, b',').is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{error::{Result, TqError}, value::{Table, Value}};
+use std::collections::BTreeSet;
+
+pub struct CsvWriter;
+
+impl CsvWriter {
+    pub fn write_table(table: &Table, delimiter: u8) -> Result<String> {
+        // Rows are unordered maps, so columns are emitted in sorted order
+        let columns: BTreeSet<&String> = table.rows.iter().flat_map(|row| row.keys()).collect();
+        
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .from_writer(Vec::new());
+        
+        writer.write_record(&columns)?;
+        for row in &table.rows {
+            let record: Vec<String> = columns
+                .iter()
+                .map(|column| row.get(*column).map(Self::format_cell).unwrap_or_default())
+                .collect();
+            writer.write_record(&record)?;
+        }
+        
+        let bytes = writer.into_inner().map_err(|e| TqError::Io(e.into_error()))?;
+        String::from_utf8(bytes).map_err(|e| TqError::Validation(e.to_string()))
+    }
+    
+    fn format_cell(value: &Value) -> String {
+        match value {
+            Value::Null => String::new(),
+            Value::Bool(b) => b.to_string(),
+            Value::Number(n) => n.to_string(),
+            Value::String(s) => s.clone(),
+            // Nested values have no CSV representation, so embed them as JSON
+            Value::Array(_) | Value::Object(_) | Value::Table(_) => {
+                value.to_json().to_string()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csv_parser::CsvParser;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_write_table() {
+        let mut row1 = HashMap::new();
+        row1.insert("id".to_string(), Value::Number(1.0));
+        row1.insert("name".to_string(), Value::String("Smith, J".to_string()));
+        
+        let mut row2 = HashMap::new();
+        row2.insert("id".to_string(), Value::Number(2.0));
+        row2.insert("name".to_string(), Value::Null);
+        
+        let table = Table {
+            name: "users".to_string(),
+            declared_rows: 2,
+            rows: vec![row1, row2],
+        };
+        
+        let csv = CsvWriter::write_table(&table, b',').unwrap();
+        assert_eq!(csv, "id,name\n1,\"Smith, J\"\n2,\n");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = "active,id,name\ntrue,1,Alice\nfalse,2,Bob\n";
+        let value = CsvParser::parse(input, b',').unwrap();
+        let tsv = CsvWriter::write_table(value.as_table().unwrap(), b'\t').unwrap();
+        
+        let reparsed = CsvParser::parse(&tsv, b'\t').unwrap();
+        assert_eq!(value, reparsed);
+    }
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Stable diagnostic codes, documented in `docs/diagnostics.md`.
+/// Codes are never reused once published.
+pub mod codes {
+    pub const PARSE_ERROR: &str = "TV0101";
+    pub const SYNTAX_ERROR: &str = "TV0102";
+    pub const JSON_PARSE_ERROR: &str = "TV0103";
+    pub const JSON_LINES_ERROR: &str = "TV0104";
+    pub const CSV_ERROR: &str = "TV0105";
+    pub const INVALID_INPUT: &str = "TV0106";
+    
+    pub const TABLE_ROW_MISMATCH: &str = "TV0201";
+    pub const TABLE_SCHEMA_INCONSISTENT: &str = "TV0202";
+    pub const TABLE_COLUMN_TYPE_MISMATCH: &str = "TV0203";
+    pub const TABLE_COLUMN_FORMAT_MISMATCH: &str = "TV0204";
+    
+    pub const EMPTY_OBJECT: &str = "TV0301";
+    pub const EMPTY_ARRAY: &str = "TV0302";
+    pub const EMPTY_TABLE: &str = "TV0303";
+    pub const DUPLICATE_KEY: &str = "TV0304";
+    pub const DANGLING_REFERENCE: &str = "TV0305";
+    pub const AMBIGUOUS_SCALAR: &str = "TV0306";
+    pub const NAMING: &str = "TV0307";
+    pub const REPEATED_KEY: &str = "TV0308";
+    
+    pub const SCHEMA_VIOLATION: &str = "TV0401";
+    
+    pub const UNUSED_SUPPRESSION: &str = "TV0501";
+    pub const INVALID_SUPPRESSION: &str = "TV0502";
+    
+    pub const TOKEN_BUDGET_EXCEEDED: &str = "TV0601";
+    pub const CONSTANT_COLUMN: &str = "TV0602";
+    pub const SPARSE_COLUMN: &str = "TV0603";
+    pub const DUPLICATE_ROW: &str = "TV0604";
+    pub const DERIVED_COLUMN: &str = "TV0605";
+    
+    /// Names for diagnostics that don't come from lint rules, so they can be
+    /// referred to like rule IDs, e.g. in suppression comments.
+    pub fn name(code: &str) -> Option<&'static str> {
+        match code {
+            TABLE_ROW_MISMATCH => Some("table-rows"),
+            TABLE_SCHEMA_INCONSISTENT => Some("table-schema"),
+            TABLE_COLUMN_TYPE_MISMATCH => Some("column-type"),
+            SCHEMA_VIOLATION => Some("schema"),
+            TOKEN_BUDGET_EXCEEDED => Some("token-budget"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A 1-based position in the source text. Columns count characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Replaces the text between `start` (inclusive) and `end` (exclusive).
+/// An empty range inserts `new_text`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub start: Span,
+    pub end: Span,
+    pub new_text: String,
+}
+
+/// Whether a fix keeps the document's data as it is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixSafety {
+    /// Repairs the syntax only, e.g. a table's declared row count
+    #[default]
+    Safe,
+    /// Changes values or key names, e.g. quoting a number; needs review
+    Suggested,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fix {
+    pub description: String,
+    pub edits: Vec<TextEdit>,
+    #[serde(default)]
+    pub safety: FixSafety,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub code: String,
+    pub severity: Severity,
+    /// ID of the lint rule that produced this diagnostic, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    pub message: String,
+    /// Location in the document, e.g. `users[1].name`; empty for the root
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize) -> Self {
+        Span { line, column }
+    }
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: &str, path: &str, message: impl Into<String>) -> Self {
+        Diagnostic {
+            code: code.to_string(),
+            severity,
+            rule: None,
+            message: message.into(),
+            path: path.to_string(),
+            span: None,
+            fix: None,
+        }
+    }
+    
+    pub fn error(code: &str, path: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, code, path, message)
+    }
+    
+    pub fn warning(code: &str, path: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, code, path, message)
+    }
+    
+    pub fn with_rule(mut self, rule: &str) -> Self {
+        self.rule = Some(rule.to_string());
+        self
+    }
+    
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+    
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "[{}] {}", self.code, self.message)
+        } else {
+            write!(f, "[{}] {}: {}", self.code, self.path, self.message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialized_shape() {
+        let diagnostic = Diagnostic::error(codes::TABLE_ROW_MISMATCH, "users", "declared 3 rows, found 2")
+            .with_span(Span::new(4, 1));
+        let json = serde_json::to_value(&diagnostic).unwrap();
+        
+        assert_eq!(json, serde_json::json!({
+            "code": "TV0201",
+            "severity": "error",
+            "message": "declared 3 rows, found 2",
+            "path": "users",
+            "span": {"line": 4, "column": 1}
+        }));
+        
+        let round_trip: Diagnostic = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip, diagnostic);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::diagnostic::{codes, Diagnostic, Span};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,24 +9,127 @@ pub enum TqError {
     #[error("Parse error at line {line}: {message}")]
     Parse { line: usize, message: String },
     
+    #[error("Parse error at line {line}, column {column}: {message}")]
+    Syntax { line: usize, column: usize, message: String },
+    
     #[error("JSON parse error: {0}")]
     JsonParse(#[from] serde_json::Error),
     
+    #[error("{}", line_errors_summary(.errors))]
+    JsonLines { errors: Vec<LineError> },
+    
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+    
     #[error("Validation error: {0}")]
     Validation(String),
     
-    #[error("Table {name} declared with {declared} rows but found {actual}")]
+    #[error("Table {name} declared with {declared} rows but found {actual}{}", extra_rows_note(.extra_lines))]
     TableRowMismatch {
         name: String,
         declared: usize,
         actual: usize,
+        /// Lines of the rows beyond the declared count, when known
+        extra_lines: Vec<usize>,
     },
     
     #[error("Inconsistent table schema in {name}: {message}")]
     TableSchemaInconsistent { name: String, message: String },
     
+    /// `row` is 1-based, matching the row numbers in other table messages
+    #[error("Table {table} column {column} row {row}: expected {expected}, found {found}")]
+    TableColumnTypeMismatch {
+        table: String,
+        column: String,
+        row: usize,
+        expected: String,
+        found: String,
+    },
+    
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+    
     #[error("Invalid input format: {0}")]
     InvalidFormat(String),
+    
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl TqError {
+    /// The stable diagnostic code for this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TqError::Parse { .. } => codes::PARSE_ERROR,
+            TqError::Syntax { .. } => codes::SYNTAX_ERROR,
+            TqError::JsonParse(_) => codes::JSON_PARSE_ERROR,
+            TqError::JsonLines { .. } => codes::JSON_LINES_ERROR,
+            TqError::Csv(_) => codes::CSV_ERROR,
+            TqError::TableRowMismatch { .. } => codes::TABLE_ROW_MISMATCH,
+            TqError::TableSchemaInconsistent { .. } => codes::TABLE_SCHEMA_INCONSISTENT,
+            TqError::TableColumnTypeMismatch { .. } => codes::TABLE_COLUMN_TYPE_MISMATCH,
+            TqError::Io(_)
+            | TqError::Validation(_)
+            | TqError::InvalidSchema(_)
+            | TqError::InvalidFormat(_)
+            | TqError::InvalidConfig(_) => codes::INVALID_INPUT,
+        }
+    }
+    
+    /// The source position of the error, when the parser reported one.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            TqError::Parse { line, .. } => Some(Span::new(*line, 1)),
+            TqError::Syntax { line, column, .. } => Some(Span::new(*line, *column)),
+            TqError::JsonParse(e) if e.line() > 0 => Some(Span::new(e.line(), e.column().max(1))),
+            TqError::Csv(e) => e.position().map(|pos| Span::new(pos.line() as usize, 1)),
+            TqError::TableRowMismatch { extra_lines, .. } => extra_lines.first().map(|line| Span::new(*line, 1)),
+            _ => None,
+        }
+    }
+    
+    /// Converts the error into diagnostics; JSON Lines errors yield one per line.
+    pub fn to_diagnostics(&self) -> Vec<Diagnostic> {
+        match self {
+            TqError::JsonLines { errors } => errors
+                .iter()
+                .map(|e| {
+                    Diagnostic::error(self.code(), "", format!("Parse error at {}", e))
+                        .with_span(Span::new(e.line, 1))
+                })
+                .collect(),
+            _ => {
+                let diagnostic = Diagnostic::error(self.code(), "", self.to_string());
+                vec![match self.span() {
+                    Some(span) => diagnostic.with_span(span),
+                    None => diagnostic,
+                }]
+            }
+        }
+    }
+}
+
+fn extra_rows_note(lines: &[usize]) -> String {
+    if lines.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = lines.iter().map(usize::to_string).collect();
+    format!("; extra rows at line{} {}", if lines.len() == 1 { "" } else { "s" }, lines.join(", "))
+}
+
+fn line_errors_summary(errors: &[LineError]) -> String {
+    match errors.first() {
+        Some(first) => format!("{} line(s) failed to parse, first at {}", errors.len(), first),
+        None => "no lines failed to parse".to_string(),
+    }
+}
+
+/// A parse failure tied to a single input line.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("line {line}: {message}")]
+pub struct LineError {
+    pub line: usize,
+    pub message: String,
 }
 
 pub type Result<T> = std::result::Result<T, TqError>;
\ No newline at end of file
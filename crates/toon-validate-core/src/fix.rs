@@ -0,0 +1,259 @@
+//! Fixes for mechanical table errors, and applying fixes to source text.
+
+use crate::{
+    diagnostic::{codes, Diagnostic, Fix, FixSafety, Span, TextEdit},
+    path,
+    source_map::SourceMap,
+    value::{Table, Value},
+};
+use std::collections::HashSet;
+
+/// The result of applying fixes to a source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixOutcome {
+    pub text: String,
+    /// Number of fixes applied
+    pub applied: usize,
+    /// Fixes left out because they overlapped an earlier one
+    pub skipped: usize,
+}
+
+/// Adds fixes to table diagnostics that have a safe mechanical repair: a
+/// header row count that disagrees with the rows, and rows missing columns
+/// that can be filled with `null`. Needs the TOON source and its source map.
+pub fn suggest_fixes(diagnostics: &mut [Diagnostic], value: &Value, source: &str, source_map: &SourceMap) {
+    let lines: Vec<&str> = source.lines().collect();
+    
+    for diagnostic in diagnostics.iter_mut().filter(|d| d.fix.is_none()) {
+        let fix = match diagnostic.code.as_str() {
+            codes::TABLE_ROW_MISMATCH => row_count_fix(&diagnostic.path, value, &lines, source_map),
+            codes::TABLE_SCHEMA_INCONSISTENT => missing_columns_fix(&diagnostic.path, value, &lines, source_map),
+            _ => None,
+        };
+        diagnostic.fix = fix;
+    }
+}
+
+fn row_count_fix(table_path: &str, value: &Value, lines: &[&str], source_map: &SourceMap) -> Option<Fix> {
+    let table = value.get_path(table_path)?.as_table()?;
+    let header = source_map.get(table_path)?;
+    let line = lines.get(header.line - 1)?;
+    
+    // The count sits between the first `[` and `]` of the header line
+    let open = line.find('[')?;
+    let close = open + line[open..].find(']')?;
+    let start = line[..open + 1].chars().count() + 1;
+    let end = line[..close].chars().count() + 1;
+    
+    Some(Fix {
+        description: format!("Set the row count to {}", table.rows.len()),
+        edits: vec![TextEdit {
+            start: Span::new(header.line, start),
+            end: Span::new(header.line, end),
+            new_text: table.rows.len().to_string(),
+        }],
+        safety: FixSafety::Safe,
+    })
+}
+
+fn missing_columns_fix(row_path: &str, value: &Value, lines: &[&str], source_map: &SourceMap) -> Option<Fix> {
+    let (table, index) = table_row(row_path, value)?;
+    let row = table.rows.get(index)?;
+    let expected: HashSet<&String> = table.rows.first()?.keys().collect();
+    
+    // Only safe when the row lacks columns; extra columns need a human decision
+    if row.keys().any(|key| !expected.contains(key)) {
+        return None;
+    }
+    let mut missing: Vec<&&String> = expected.iter().filter(|key| !row.contains_key(**key)).collect();
+    missing.sort();
+    if missing.is_empty() {
+        return None;
+    }
+    
+    // New fields go right after the `-` line, indented like the row's fields
+    let marker = source_map.get(row_path)?;
+    let line = lines.get(marker.line - 1)?;
+    let indent = " ".repeat(marker.column + 1);
+    let new_text: String = missing
+        .iter()
+        .map(|key| format!("\n{}{}: null", indent, key))
+        .collect();
+    let end_of_line = Span::new(marker.line, line.chars().count() + 1);
+    
+    Some(Fix {
+        description: format!(
+            "Add {} as null",
+            missing.iter().map(|key| key.as_str()).collect::<Vec<_>>().join(", ")
+        ),
+        edits: vec![TextEdit {
+            start: end_of_line,
+            end: end_of_line,
+            new_text,
+        }],
+        safety: FixSafety::Safe,
+    })
+}
+
+/// Splits a row path such as `users[2]` into its table and row index.
+fn table_row<'a>(row_path: &str, value: &'a Value) -> Option<(&'a Table, usize)> {
+    let table_path = path::parent(row_path)?;
+    let index = row_path[table_path.len()..]
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()?;
+    Some((value.get_path(table_path)?.as_table()?, index))
+}
+
+/// Applies the fixes attached to `diagnostics` that are at most as risky as
+/// `max_safety`. Each fix is applied whole or not at all; a fix whose edits
+/// overlap an earlier fix is skipped.
+pub fn apply_fixes(source: &str, diagnostics: &[Diagnostic], max_safety: FixSafety) -> FixOutcome {
+    let line_starts = line_starts(source);
+    let mut accepted: Vec<(usize, usize, &str)> = Vec::new();
+    let mut applied = 0;
+    let mut skipped = 0;
+    
+    for fix in diagnostics.iter().filter_map(|d| d.fix.as_ref()).filter(|fix| fix.safety <= max_safety) {
+        let ranges: Vec<(usize, usize, &str)> = fix
+            .edits
+            .iter()
+            .map(|edit| {
+                let start = offset(source, &line_starts, edit.start);
+                let end = offset(source, &line_starts, edit.end).max(start);
+                (start, end, edit.new_text.as_str())
+            })
+            .collect();
+    
+        let conflicts = ranges.iter().any(|&(start, end, _)| {
+            accepted
+                .iter()
+                .any(|&(s, e, _)| (start < e && s < end) || start == s || (start == end && start == e))
+        });
+        if conflicts {
+            skipped += 1;
+            continue;
+        }
+        accepted.extend(ranges);
+        applied += 1;
+    }
+    
+    // Apply back to front so earlier offsets stay valid
+    accepted.sort_by_key(|&(start, end, _)| std::cmp::Reverse((start, end)));
+    let mut text = source.to_string();
+    for (start, end, new_text) in accepted {
+        text.replace_range(start..end, new_text);
+    }
+    
+    FixOutcome { text, applied, skipped }
+}
+
+fn line_starts(source: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect()
+}
+
+/// Converts a 1-based line and character column to a byte offset, clamping
+/// to the end of the line or the source.
+fn offset(source: &str, line_starts: &[usize], span: Span) -> usize {
+    let Some(&line_start) = line_starts.get(span.line.saturating_sub(1)) else {
+        return source.len();
+    };
+    let line_end = source[line_start..]
+        .find('\n')
+        .map_or(source.len(), |idx| line_start + idx);
+    let line = source[line_start..line_end].trim_end_matches('\r');
+    
+    line.char_indices()
+        .nth(span.column.saturating_sub(1))
+        .map_or(line_start + line.len(), |(idx, _)| line_start + idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{toon_parser::ToonParser, validator::Validator};
+
+    fn fix(input: &str) -> FixOutcome {
+        let (value, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        let mut diagnostics = Validator::validate_all(&value);
+        suggest_fixes(&mut diagnostics, &value, input, &source_map);
+        apply_fixes(input, &diagnostics, FixSafety::Safe)
+    }
+
+    #[test]
+    fn test_fixes_row_count() {
+        let outcome = fix(r#"users[3]:
+  - id: 1
+  - id: 2
+"#);
+        assert_eq!(outcome.applied, 1);
+        assert_eq!(outcome.text, r#"users[2]:
+  - id: 1
+  - id: 2
+"#);
+    }
+
+    #[test]
+    fn test_fills_missing_columns_with_null() {
+        let outcome = fix(r#"users[3]:
+  - id: 1
+    name: "Alice"
+    email: "a@example.com"
+  - id: 2
+  -
+    name: "Carol"
+    email: "c@example.com""#);
+        assert_eq!(outcome.applied, 2);
+        assert_eq!(outcome.text, r#"users[3]:
+  - id: 1
+    name: "Alice"
+    email: "a@example.com"
+  - id: 2
+    email: null
+    name: null
+  -
+    id: null
+    name: "Carol"
+    email: "c@example.com""#);
+    
+        let (value, _) = ToonParser::parse_with_source_map(&outcome.text).unwrap();
+        assert!(Validator::validate(&value).is_ok());
+    }
+
+    #[test]
+    fn test_extra_columns_are_not_fixed() {
+        let outcome = fix(r#"users[2]:
+  - id: 1
+  - id: 2
+    extra: true"#);
+        assert_eq!(outcome.applied, 0);
+    }
+
+    #[test]
+    fn test_overlapping_fixes_are_skipped() {
+        let edit = |text: &str, safety: FixSafety| {
+            Diagnostic::error("X", "", "x").with_fix(Fix {
+                description: text.to_string(),
+                edits: vec![TextEdit { start: Span::new(1, 1), end: Span::new(1, 4), new_text: text.to_string() }],
+                safety,
+            })
+        };
+        let outcome = apply_fixes("abc\ndef", &[edit("xyz", FixSafety::Safe), edit("uvw", FixSafety::Safe)], FixSafety::Safe);
+        assert_eq!(outcome.text, "xyz\ndef");
+        assert_eq!((outcome.applied, outcome.skipped), (1, 1));
+    }
+
+    #[test]
+    fn test_suggested_fixes_need_opting_in() {
+        let diagnostics = [Diagnostic::error("X", "", "x").with_fix(Fix {
+            description: "Quote abc".to_string(),
+            edits: vec![TextEdit { start: Span::new(1, 1), end: Span::new(1, 4), new_text: "\"abc\"".to_string() }],
+            safety: FixSafety::Suggested,
+        })];
+        assert_eq!(apply_fixes("abc", &diagnostics, FixSafety::Safe).applied, 0);
+        assert_eq!(apply_fixes("abc", &diagnostics, FixSafety::Suggested).text, "\"abc\"");
+    }
+}
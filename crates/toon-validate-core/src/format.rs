@@ -0,0 +1,171 @@
+use crate::error::{Result, TqError};
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// A well-known string format, named as in JSON Schema's `format` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StringFormat {
+    /// RFC 3339 full date, e.g. `2024-02-29`
+    Date,
+    /// RFC 3339 timestamp, e.g. `2024-02-29T13:45:00Z`
+    DateTime,
+    /// RFC 3339 time with an offset, e.g. `13:45:00+02:00`
+    Time,
+    Email,
+    /// URI with `scheme://` and a host, e.g. `https://example.com/a`, or a
+    /// `mailto:`, `urn:`, `tel:` or `data:` URI
+    Uri,
+    /// Hyphenated UUID, e.g. `123e4567-e89b-12d3-a456-426614174000`
+    Uuid,
+}
+
+impl StringFormat {
+    /// Every format, most specific first, so detection picks `date-time`
+    /// rather than anything looser.
+    pub const ALL: [StringFormat; 6] = [
+        StringFormat::DateTime,
+        StringFormat::Date,
+        StringFormat::Time,
+        StringFormat::Uuid,
+        StringFormat::Email,
+        StringFormat::Uri,
+    ];
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            StringFormat::Date => "date",
+            StringFormat::DateTime => "date-time",
+            StringFormat::Time => "time",
+            StringFormat::Email => "email",
+            StringFormat::Uri => "uri",
+            StringFormat::Uuid => "uuid",
+        }
+    }
+    
+    /// The first format `s` matches, if any.
+    pub fn detect(s: &str) -> Option<StringFormat> {
+        Self::ALL.into_iter().find(|format| format.matches(s))
+    }
+    
+    pub fn matches(&self, s: &str) -> bool {
+        match self {
+            StringFormat::Date => is_date(s),
+            StringFormat::DateTime => {
+                // RFC 3339 allows a space separator and lowercase letters
+                match s.find(['T', 't', ' ']) {
+                    Some(idx) => is_date(&s[..idx]) && is_time(&s[idx + 1..]),
+                    None => false,
+                }
+            }
+            StringFormat::Time => is_time(s),
+            StringFormat::Email => pattern(&EMAIL, r"^[^\s@]+@[^\s@.]+(\.[^\s@.]+)+$").is_match(s),
+            // A bare `word:rest` is too common in prose ("Re: lunch") to count
+            StringFormat::Uri => pattern(
+                &URI,
+                r"^(?:[A-Za-z][A-Za-z0-9+.\-]*://[^\s/?#]+[^\s]*|(?i:mailto|urn|tel|data):[^\s]+)$",
+            )
+            .is_match(s),
+            StringFormat::Uuid => pattern(&UUID, r"^(?i)[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$").is_match(s),
+        }
+    }
+}
+
+impl FromStr for StringFormat {
+    type Err = TqError;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "date" => Ok(StringFormat::Date),
+            "date-time" => Ok(StringFormat::DateTime),
+            "time" => Ok(StringFormat::Time),
+            "email" => Ok(StringFormat::Email),
+            "uri" => Ok(StringFormat::Uri),
+            "uuid" => Ok(StringFormat::Uuid),
+            other => Err(TqError::InvalidSchema(format!("unknown string format '{}'", other))),
+        }
+    }
+}
+
+impl fmt::Display for StringFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+static EMAIL: OnceLock<Regex> = OnceLock::new();
+static URI: OnceLock<Regex> = OnceLock::new();
+static UUID: OnceLock<Regex> = OnceLock::new();
+static DATE: OnceLock<Regex> = OnceLock::new();
+static TIME: OnceLock<Regex> = OnceLock::new();
+
+fn pattern(cell: &'static OnceLock<Regex>, source: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(source).expect("format pattern is valid"))
+}
+
+fn is_date(s: &str) -> bool {
+    let Some(caps) = pattern(&DATE, r"^(\d{4})-(\d{2})-(\d{2})$").captures(s) else {
+        return false;
+    };
+    let number = |idx: usize| caps[idx].parse::<u32>().unwrap_or(0);
+    let (year, month, day) = (number(1), number(2), number(3));
+    
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+fn is_time(s: &str) -> bool {
+    let Some(caps) = pattern(&TIME, r"^(\d{2}):(\d{2}):(\d{2})(\.\d+)?([Zz]|[+-](\d{2}):(\d{2}))$").captures(s) else {
+        return false;
+    };
+    let number = |idx: usize| caps.get(idx).map_or(0, |m| m.as_str().parse::<u32>().unwrap_or(99));
+    // Second 60 is a leap second
+    number(1) < 24 && number(2) < 60 && number(3) <= 60 && number(6) < 24 && number(7) < 60
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_formats() {
+        assert!(StringFormat::Date.matches("2024-02-29"));
+        assert!(!StringFormat::Date.matches("2023-02-29"));
+        assert!(!StringFormat::Date.matches("2024-13-01"));
+        assert!(StringFormat::DateTime.matches("2024-02-29T13:45:00Z"));
+        assert!(StringFormat::DateTime.matches("2024-02-29 13:45:00.123+02:00"));
+        assert!(!StringFormat::DateTime.matches("2024-02-29T25:00:00Z"));
+        assert!(!StringFormat::DateTime.matches("yesterday"));
+        assert!(StringFormat::Time.matches("23:59:60z"));
+        assert!(StringFormat::Email.matches("ada@example.com"));
+        assert!(!StringFormat::Email.matches("ada@localhost"));
+        assert!(StringFormat::Uri.matches("https://example.com/a?b=c"));
+        assert!(StringFormat::Uri.matches("mailto:ada@example.com"));
+        assert!(StringFormat::Uri.matches("urn:isbn:0451450523"));
+        assert!(!StringFormat::Uri.matches("example.com"));
+        assert!(!StringFormat::Uri.matches("TODO:fix"));
+        assert!(!StringFormat::Uri.matches("https://"));
+        assert!(StringFormat::Uuid.matches("123E4567-e89b-12d3-a456-426614174000"));
+        assert!(!StringFormat::Uuid.matches("123e4567e89b12d3a456426614174000"));
+    }
+
+    #[test]
+    fn test_detect_and_names() {
+        assert_eq!(StringFormat::detect("2024-01-05T10:00:00Z"), Some(StringFormat::DateTime));
+        assert_eq!(StringFormat::detect("2024-01-05"), Some(StringFormat::Date));
+        assert_eq!(StringFormat::detect("ada@example.com"), Some(StringFormat::Email));
+        assert_eq!(StringFormat::detect("Ada Lovelace"), None);
+        for format in StringFormat::ALL {
+            assert_eq!(format.name().parse::<StringFormat>().unwrap(), format);
+        }
+        assert!("ipv4".parse::<StringFormat>().is_err());
+    }
+}
@@ -0,0 +1,432 @@
+use crate::{error::{Result, TqError}, value::Value};
+use std::collections::HashMap;
+
+/// How deeply objects and arrays may nest, as in serde_json. Deeper input
+/// is rejected rather than overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Relaxed JSON parser accepting the JSON5/JSONC extensions seen in
+/// hand-written fixtures: comments, trailing commas, single-quoted
+/// strings, unquoted keys, hexadecimal numbers and `Infinity`/`NaN`.
+pub struct Json5Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+    depth: usize,
+}
+
+impl Json5Parser {
+    pub fn parse(input: &str) -> Result<Value> {
+        let mut parser = Json5Parser {
+            chars: input.chars().collect(),
+            pos: 0,
+            line: 1,
+            column: 1,
+            depth: 0,
+        };
+        
+        parser.skip_whitespace()?;
+        let value = parser.parse_value()?;
+        parser.skip_whitespace()?;
+        
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!("Unexpected trailing character '{}'", c)));
+        }
+        
+        Ok(value)
+    }
+    
+    fn parse_value(&mut self) -> Result<Value> {
+        match self.peek() {
+            Some('{') => self.nested(Self::parse_object),
+            Some('[') => self.nested(Self::parse_array),
+            Some('"') | Some('\'') => Ok(Value::String(self.parse_string()?)),
+            Some(c) if c == '-' || c == '+' || c == '.' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) if Self::is_identifier_start(c) => {
+                let word = self.parse_identifier();
+                match word.as_str() {
+                    "null" => Ok(Value::Null),
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    "Infinity" => Ok(Value::Number(f64::INFINITY)),
+                    "NaN" => Ok(Value::Number(f64::NAN)),
+                    _ => Err(self.error(format!("Unexpected identifier '{}'", word))),
+                }
+            }
+            Some(c) => Err(self.error(format!("Unexpected character '{}'", c))),
+            None => Err(self.error("Unexpected end of input".to_string())),
+        }
+    }
+    
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Value>) -> Result<Value> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(format!("Nesting deeper than {} levels", MAX_DEPTH)));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+    
+    fn parse_object(&mut self) -> Result<Value> {
+        self.expect('{')?;
+        let mut obj = HashMap::new();
+        
+        loop {
+            self.skip_whitespace()?;
+            if self.peek() == Some('}') {
+                self.advance();
+                return Ok(Value::Object(obj));
+            }
+            
+            let key = match self.peek() {
+                Some('"') | Some('\'') => self.parse_string()?,
+                Some(c) if Self::is_identifier_start(c) => self.parse_identifier(),
+                Some(c) => return Err(self.error(format!("Expected object key, found '{}'", c))),
+                None => return Err(self.error("Unterminated object".to_string())),
+            };
+            
+            self.skip_whitespace()?;
+            self.expect(':')?;
+            self.skip_whitespace()?;
+            let value = self.parse_value()?;
+            obj.insert(key, value);
+            
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(',') => {
+                    self.advance();
+                }
+                Some('}') => {}
+                Some(c) => return Err(self.error(format!("Expected ',' or '}}', found '{}'", c))),
+                None => return Err(self.error("Unterminated object".to_string())),
+            }
+        }
+    }
+    
+    fn parse_array(&mut self) -> Result<Value> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        
+        loop {
+            self.skip_whitespace()?;
+            if self.peek() == Some(']') {
+                self.advance();
+                return Ok(Value::Array(items));
+            }
+            
+            items.push(self.parse_value()?);
+            
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(',') => {
+                    self.advance();
+                }
+                Some(']') => {}
+                Some(c) => return Err(self.error(format!("Expected ',' or ']', found '{}'", c))),
+                None => return Err(self.error("Unterminated array".to_string())),
+            }
+        }
+    }
+    
+    fn parse_string(&mut self) -> Result<String> {
+        let quote = self.advance().unwrap_or('"');
+        let mut result = String::new();
+        
+        loop {
+            match self.advance() {
+                Some(c) if c == quote => return Ok(result),
+                Some('\\') => match self.advance() {
+                    Some('n') => result.push('\n'),
+                    Some('t') => result.push('\t'),
+                    Some('r') => result.push('\r'),
+                    Some('b') => result.push('\u{8}'),
+                    Some('f') => result.push('\u{c}'),
+                    Some('0') => result.push('\0'),
+                    Some('x') => result.push(char::from(self.parse_hex(2, 'x')? as u8)),
+                    Some('u') => result.push(self.parse_unicode_escape()?),
+                    // Escaped line break continues the string on the next line
+                    Some('\n') => {}
+                    Some(c) => result.push(c),
+                    None => return Err(self.error("Unterminated string".to_string())),
+                },
+                Some('\n') | None => return Err(self.error("Unterminated string".to_string())),
+                Some(c) => result.push(c),
+            }
+        }
+    }
+    
+    /// Reads the code point after `\u`. A UTF-16 high surrogate must be
+    /// followed by a `\u` escaped low surrogate; the pair forms one character.
+    fn parse_unicode_escape(&mut self) -> Result<char> {
+        let high = self.parse_hex(4, 'u')?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error(format!("Lone surrogate '\\u{:04x}'", high)));
+        }
+        
+        if self.peek() != Some('\\') || self.peek_at(1) != Some('u') {
+            return Err(self.error(format!("Lone surrogate '\\u{:04x}'", high)));
+        }
+        self.advance();
+        self.advance();
+        let low = self.parse_hex(4, 'u')?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error(format!("Lone surrogate '\\u{:04x}'", high)));
+        }
+        
+        let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+        char::from_u32(code).ok_or_else(|| self.error(format!("Invalid unicode escape '\\u{:04x}'", code)))
+    }
+    
+    /// Reads the `digits` hex digits of a `\x` or `\u` escape.
+    fn parse_hex(&mut self, digits: usize, escape: char) -> Result<u32> {
+        let hex: String = (0..digits).filter_map(|_| self.advance()).collect();
+        if hex.len() != digits || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(self.error(format!("Invalid escape '\\{}{}'", escape, hex)));
+        }
+        Ok(u32::from_str_radix(&hex, 16).unwrap_or(0))
+    }
+    
+    fn parse_number(&mut self) -> Result<Value> {
+        let start_line = self.line;
+        let start_column = self.column;
+        let mut text = String::new();
+        
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.') {
+                text.push(c);
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        
+        let (sign, digits) = match text.strip_prefix('-') {
+            Some(rest) => (-1.0, rest),
+            None => (1.0, text.strip_prefix('+').unwrap_or(&text)),
+        };
+        
+        let parsed = if let Some(hex) = digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+            i64::from_str_radix(hex, 16).ok().map(|n| n as f64)
+        } else if digits == "Infinity" {
+            Some(f64::INFINITY)
+        } else if digits == "NaN" {
+            Some(f64::NAN)
+        } else {
+            // Overflows to infinity, as in JavaScript and like the `Infinity` literal
+            digits.parse::<f64>().ok()
+        };
+        
+        match parsed {
+            Some(n) => Ok(Value::Number(sign * n)),
+            None => Err(TqError::Syntax {
+                line: start_line,
+                column: start_column,
+                message: format!("Invalid number '{}'", text),
+            }),
+        }
+    }
+    
+    fn parse_identifier(&mut self) -> String {
+        let mut ident = String::new();
+        while let Some(c) = self.peek() {
+            if Self::is_identifier_start(c) || c.is_ascii_digit() {
+                ident.push(c);
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        ident
+    }
+    
+    fn skip_whitespace(&mut self) -> Result<()> {
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(c), _) if c.is_whitespace() || c == '\u{feff}' => {
+                    self.advance();
+                }
+                (Some('/'), Some('/')) => {
+                    while let Some(c) = self.peek() {
+                        if c == '\n' {
+                            break;
+                        }
+                        self.advance();
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    let (line, column) = (self.line, self.column);
+                    self.advance();
+                    self.advance();
+                    loop {
+                        match (self.peek(), self.peek_at(1)) {
+                            (Some('*'), Some('/')) => {
+                                self.advance();
+                                self.advance();
+                                break;
+                            }
+                            (Some(_), _) => {
+                                self.advance();
+                            }
+                            (None, _) => {
+                                return Err(TqError::Syntax {
+                                    line,
+                                    column,
+                                    message: "Unterminated block comment".to_string(),
+                                });
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+    
+    fn expect(&mut self, expected: char) -> Result<()> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.advance();
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("Expected '{}', found '{}'", expected, c))),
+            None => Err(self.error(format!("Expected '{}', found end of input", expected))),
+        }
+    }
+    
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+    
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+    
+    fn advance(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+    
+    fn is_identifier_start(c: char) -> bool {
+        c.is_alphabetic() || c == '_' || c == '$'
+    }
+    
+    fn error(&self, message: String) -> TqError {
+        TqError::Syntax {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_surrogate_pair_escapes() {
+        let result = Json5Parser::parse(r#""\ud83d\ude00 \u00e9""#).unwrap();
+        assert_eq!(result, Value::String("\u{1f600} \u{e9}".to_string()));
+        
+        assert!(Json5Parser::parse(r#""\ud83d""#).is_err());
+        assert!(Json5Parser::parse(r#""\ud83dx""#).is_err());
+        assert!(Json5Parser::parse(r#""\ude00""#).is_err());
+        assert!(Json5Parser::parse(r#""\ud83d\u0041""#).is_err());
+    }
+
+    #[test]
+    fn test_hex_escapes_and_overflow() {
+        let result = Json5Parser::parse(r#"["\x41\xe9", 1e999, -1e999]"#).unwrap();
+        assert_eq!(
+            result,
+            Value::Array(vec![
+                Value::String("A\u{e9}".to_string()),
+                Value::Number(f64::INFINITY),
+                Value::Number(f64::NEG_INFINITY),
+            ])
+        );
+        assert!(Json5Parser::parse(r#""\x4""#).is_err());
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = format!("{}1{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        assert!(Json5Parser::parse(&nested).is_ok());
+        
+        let result = Json5Parser::parse(&"[".repeat(200_000));
+        match result {
+            Err(TqError::Syntax { line, column, message }) => {
+                assert_eq!((line, column), (1, MAX_DEPTH + 1));
+                assert_eq!(message, "Nesting deeper than 128 levels");
+            }
+            other => panic!("Expected syntax error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_plain_json() {
+        let result = Json5Parser::parse(r#"{"name": "test", "tags": [1, 2.5, true, null]}"#).unwrap();
+        let obj = result.as_object().unwrap();
+        
+        assert_eq!(obj.get("name"), Some(&Value::String("test".to_string())));
+        assert_eq!(
+            obj.get("tags"),
+            Some(&Value::Array(vec![
+                Value::Number(1.0),
+                Value::Number(2.5),
+                Value::Bool(true),
+                Value::Null,
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parse_relaxed_syntax() {
+        let input = r#"// prompt fixture
+{
+  /* block
+     comment */
+  name: 'it\'s',
+  'quoted': "x", // trailing comment
+  hex: 0x1F,
+  list: [1, 2,],
+}"#;
+        let result = Json5Parser::parse(input).unwrap();
+        let obj = result.as_object().unwrap();
+        
+        assert_eq!(obj.get("name"), Some(&Value::String("it's".to_string())));
+        assert_eq!(obj.get("quoted"), Some(&Value::String("x".to_string())));
+        assert_eq!(obj.get("hex"), Some(&Value::Number(31.0)));
+        assert_eq!(obj.get("list"), Some(&Value::Array(vec![Value::Number(1.0), Value::Number(2.0)])));
+    }
+
+    #[test]
+    fn test_syntax_error_span() {
+        let input = "{\n  a: 1,\n  b 2\n}";
+        let result = Json5Parser::parse(input);
+        
+        match result {
+            Err(TqError::Syntax { line, column, message }) => {
+                assert_eq!(line, 3);
+                assert_eq!(column, 5);
+                assert!(message.contains("Expected ':'"));
+            }
+            other => panic!("Expected syntax error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unterminated_comment() {
+        let result = Json5Parser::parse("{ /* never closed");
+        assert!(matches!(result, Err(TqError::Syntax { line: 1, column: 3, .. })));
+    }
+}
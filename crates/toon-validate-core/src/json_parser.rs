@@ -9,7 +9,7 @@ impl JsonParser {
         Ok(Self::convert_json_to_value(json_value))
     }
     
-    fn convert_json_to_value(json: serde_json::Value) -> Value {
+    pub(crate) fn convert_json_to_value(json: serde_json::Value) -> Value {
         match json {
             serde_json::Value::Null => Value::Null,
             serde_json::Value::Bool(b) => Value::Bool(b),
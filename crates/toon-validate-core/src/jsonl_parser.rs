@@ -0,0 +1,144 @@
+use crate::{
+    error::{LineError, Result, TqError},
+    json_parser::JsonParser,
+    value::{Table, Value},
+};
+use std::collections::HashSet;
+
+pub struct JsonLinesParser;
+
+/// Records and per-line failures from a JSON Lines document.
+#[derive(Debug, Clone)]
+pub struct JsonLinesReport {
+    /// Parsed records with their 1-based line numbers
+    pub records: Vec<(usize, Value)>,
+    pub errors: Vec<LineError>,
+}
+
+impl JsonLinesParser {
+    pub const TABLE_NAME: &'static str = "records";
+    
+    pub fn parse(input: &str) -> Result<Value> {
+        Self::parse_with(input, false)
+    }
+    
+    /// Parses every line and fails with all line errors if any line is invalid.
+    /// With `fold` set, uniform object records are returned as a single table.
+    pub fn parse_with(input: &str, fold: bool) -> Result<Value> {
+        let report = Self::parse_lines(input);
+        if !report.errors.is_empty() {
+            return Err(TqError::JsonLines { errors: report.errors });
+        }
+        
+        let records = Value::Array(report.records.into_iter().map(|(_, v)| v).collect());
+        Ok(if fold { Self::fold(records) } else { records })
+    }
+    
+    pub fn parse_lines(input: &str) -> JsonLinesReport {
+        let mut records = Vec::new();
+        let mut errors = Vec::new();
+        
+        for (idx, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            
+            match JsonParser::parse(line) {
+                Ok(value) => records.push((idx + 1, value)),
+                Err(e) => {
+                    let message = match e {
+                        TqError::JsonParse(json_err) => json_err.to_string(),
+                        other => other.to_string(),
+                    };
+                    errors.push(LineError { line: idx + 1, message });
+                }
+            }
+        }
+        
+        JsonLinesReport { records, errors }
+    }
+    
+    /// Converts an array of objects sharing the same keys into a table.
+    /// Any other value is returned unchanged.
+    pub fn fold(value: Value) -> Value {
+        match value {
+            Value::Array(items) => match Self::fold_records(&items) {
+                Some(table) => Value::Table(table),
+                None => Value::Array(items),
+            },
+            other => other,
+        }
+    }
+    
+    pub fn fold_records(records: &[Value]) -> Option<Table> {
+        let first = records.first()?.as_object()?;
+        let keys: HashSet<&String> = first.keys().collect();
+        
+        let mut rows = Vec::with_capacity(records.len());
+        for record in records {
+            let obj = record.as_object()?;
+            if obj.len() != keys.len() || !obj.keys().all(|k| keys.contains(k)) {
+                return None;
+            }
+            rows.push(obj.clone());
+        }
+        
+        Some(Table {
+            name: Self::TABLE_NAME.to_string(),
+            declared_rows: rows.len(),
+            rows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "{\"id\": 1}\n\n{\"id\": 2}\n";
+        let result = JsonLinesParser::parse(input).unwrap();
+        
+        if let Value::Array(items) = result {
+            assert_eq!(items.len(), 2);
+        } else {
+            panic!("Expected array");
+        }
+    }
+
+    #[test]
+    fn test_reports_every_bad_line() {
+        let input = "{\"id\": 1}\n{\"id\": \n{\"id\": 3}\nnot json\n";
+        let report = JsonLinesParser::parse_lines(input);
+        
+        assert_eq!(report.records.len(), 2);
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(report.errors[0].line, 2);
+        assert_eq!(report.errors[1].line, 4);
+        
+        let result = JsonLinesParser::parse(input);
+        assert!(matches!(result, Err(TqError::JsonLines { ref errors }) if errors.len() == 2));
+        assert!(result.unwrap_err().to_string().starts_with("2 line(s) failed to parse, first at line 2"));
+        assert_eq!(TqError::JsonLines { errors: Vec::new() }.to_string(), "no lines failed to parse");
+    }
+
+    #[test]
+    fn test_fold_uniform_records() {
+        let input = "{\"id\": 1, \"name\": \"a\"}\n{\"name\": \"b\", \"id\": 2}\n";
+        let result = JsonLinesParser::parse_with(input, true).unwrap();
+        
+        let table = result.as_table().expect("Expected table");
+        assert_eq!(table.name, "records");
+        assert_eq!(table.declared_rows, 2);
+        assert_eq!(table.rows[1].get("name"), Some(&Value::String("b".to_string())));
+    }
+
+    #[test]
+    fn test_fold_keeps_non_uniform_records() {
+        let input = "{\"id\": 1}\n{\"id\": 2, \"extra\": true}\n";
+        let result = JsonLinesParser::parse_with(input, true).unwrap();
+        
+        assert!(matches!(result, Value::Array(_)));
+    }
+}
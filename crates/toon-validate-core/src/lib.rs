@@ -1,13 +1,39 @@
+pub mod csv_parser;
+pub mod csv_writer;
+pub mod diagnostic;
 pub mod error;
+pub mod fix;
+pub mod format;
+pub mod json5_parser;
 pub mod json_parser;
+pub mod jsonl_parser;
 pub mod parser;
+pub mod path;
+pub mod rules;
+pub mod schema;
+pub mod schema_diff;
+pub mod schema_inference;
+pub mod source_map;
+pub mod suppression;
 pub mod token_estimator;
+pub mod toml_parser;
 pub mod toon_parser;
 pub mod validator;
 pub mod value;
 
-pub use error::{Result, TqError};
-pub use parser::{InputFormat, Parser};
-pub use token_estimator::{TokenBreakdown, TokenEstimator};
-pub use validator::Validator;
+pub use csv_writer::CsvWriter;
+pub use diagnostic::{Diagnostic, Fix, FixSafety, Severity, Span, TextEdit};
+pub use fix::{apply_fixes, suggest_fixes, FixOutcome};
+pub use format::StringFormat;
+pub use error::{LineError, Result, TqError};
+pub use jsonl_parser::JsonLinesParser;
+pub use parser::{Confidence, Detection, InputFormat, Parser};
+pub use rules::{Rule, RuleContext, RuleLevel, RuleOverride, RuleRegistry};
+pub use schema::{Schema, SchemaViolation};
+pub use schema_diff::{diff_schemas, ChangeKind, SchemaChange};
+pub use schema_inference::{InferenceOptions, SchemaInferrer};
+pub use source_map::{Comment, DuplicateKey, SourceMap, UnquotedScalar};
+pub use suppression::Suppressions;
+pub use token_estimator::{BudgetOverrun, TokenBreakdown, TokenEstimator};
+pub use validator::{ColumnTypePolicy, ValidationProfile, Validator, ValidatorBuilder, ValidatorOptions};
 pub use value::{Table, Value};
\ No newline at end of file
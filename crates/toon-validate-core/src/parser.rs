@@ -1,24 +1,95 @@
 use crate::{
+    csv_parser::CsvParser,
     error::{Result, TqError},
+    json5_parser::Json5Parser,
     json_parser::JsonParser,
+    jsonl_parser::JsonLinesParser,
+    source_map::SourceMap,
+    toml_parser::TomlParser,
     toon_parser::ToonParser,
     value::Value,
 };
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputFormat {
     Toon,
     Json,
+    Json5,
+    JsonLines,
+    Csv,
+    Tsv,
+    Toml,
+}
+
+impl FromStr for InputFormat {
+    type Err = TqError;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        InputFormat::from_str(s)
+    }
 }
 
 impl InputFormat {
+    /// Kept alongside the `FromStr` impl so callers don't need the trait in scope.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self> {
         match s.to_lowercase().as_str() {
             "toon" => Ok(InputFormat::Toon),
             "json" => Ok(InputFormat::Json),
+            "json5" | "jsonc" => Ok(InputFormat::Json5),
+            "jsonl" | "ndjson" => Ok(InputFormat::JsonLines),
+            "csv" => Ok(InputFormat::Csv),
+            "tsv" => Ok(InputFormat::Tsv),
+            "toml" => Ok(InputFormat::Toml),
             _ => Err(TqError::InvalidFormat(format!("Unknown format: {}", s))),
         }
     }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputFormat::Toon => "toon",
+            InputFormat::Json => "json",
+            InputFormat::Json5 => "json5",
+            InputFormat::JsonLines => "jsonl",
+            InputFormat::Csv => "csv",
+            InputFormat::Tsv => "tsv",
+            InputFormat::Toml => "toml",
+        }
+    }
+    
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        ext.parse().ok()
+    }
+}
+
+/// How sure format detection is about its choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// Nothing parsed; the format is a best guess
+    Low,
+    /// The format parses, but only one signal supports it
+    Medium,
+    /// Extension and content agree and the content parses
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    pub format: InputFormat,
+    pub confidence: Confidence,
+    /// Format implied by the file extension, if it is a known one
+    pub extension_format: Option<InputFormat>,
+    /// Format suggested by sniffing the start of the content
+    pub content_format: InputFormat,
+}
+
+impl Detection {
+    /// True when the extension names a format the content does not match.
+    pub fn is_conflict(&self) -> bool {
+        self.extension_format
+            .is_some_and(|ext| !Parser::formats_agree(ext, self.format))
+    }
 }
 
 pub struct Parser;
@@ -28,15 +99,169 @@ impl Parser {
         match format {
             InputFormat::Toon => ToonParser::parse(input),
             InputFormat::Json => JsonParser::parse(input),
+            InputFormat::Json5 => Json5Parser::parse(input),
+            InputFormat::JsonLines => JsonLinesParser::parse(input),
+            InputFormat::Csv => CsvParser::parse(input, b','),
+            InputFormat::Tsv => CsvParser::parse(input, b'\t'),
+            InputFormat::Toml => TomlParser::parse(input),
+        }
+    }
+    
+    /// Parses and also returns source positions. Only TOON input records
+    /// positions; other formats return an empty map.
+    pub fn parse_with_source_map(input: &str, format: InputFormat) -> Result<(Value, SourceMap)> {
+        match format {
+            InputFormat::Toon => ToonParser::parse_with_source_map(input),
+            _ => Self::parse(input, format).map(|value| (value, SourceMap::new())),
         }
     }
     
     pub fn detect_format(input: &str) -> InputFormat {
-        let trimmed = input.trim();
-        if trimmed.starts_with('{') || trimmed.starts_with('[') {
-            InputFormat::Json
-        } else {
-            InputFormat::Toon
+        Self::detect(input, None).format
+    }
+    
+    /// Combines the file extension, a content sniff and a trial parse.
+    /// Candidates are tried in order (extension first, then sniffed formats)
+    /// and the first one that parses wins.
+    pub fn detect(input: &str, extension: Option<&str>) -> Detection {
+        let extension_format = extension.and_then(InputFormat::from_extension);
+        let sniffed = Self::sniff(input);
+        let content_format = sniffed[0];
+        
+        let mut candidates: Vec<InputFormat> = extension_format.into_iter().collect();
+        candidates.extend(sniffed.iter().copied().filter(|f| Some(*f) != extension_format));
+        
+        let parsed = candidates
+            .iter()
+            .copied()
+            .find(|format| Self::parse(input, *format).is_ok());
+        
+        let (format, confidence) = match (parsed, extension_format) {
+            (Some(format), Some(ext)) if Self::formats_agree(ext, format)
+                && Self::formats_agree(ext, content_format) => (format, Confidence::High),
+            (Some(format), _) => (format, Confidence::Medium),
+            (None, Some(ext)) => (ext, Confidence::Low),
+            (None, None) => (content_format, Confidence::Low),
+        };
+        
+        Detection {
+            format,
+            confidence,
+            extension_format,
+            content_format,
+        }
+    }
+    
+    /// JSON5 is a superset of JSON, so a `.json` file holding JSONC is not a conflict.
+    fn formats_agree(extension: InputFormat, format: InputFormat) -> bool {
+        extension == format || (extension == InputFormat::Json && format == InputFormat::Json5)
+    }
+    
+    /// Returns likely formats for the content, most likely first.
+    fn sniff(input: &str) -> Vec<InputFormat> {
+        let trimmed = input.trim_start_matches('\u{feff}').trim();
+        let lines: Vec<&str> = trimmed
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+        let first = lines.first().copied().unwrap_or("");
+        
+        if trimmed.starts_with("//") || trimmed.starts_with("/*") {
+            return vec![InputFormat::Json5, InputFormat::Json];
+        }
+        
+        if first.starts_with('{') {
+            // One complete object per line is JSON Lines
+            if lines.len() > 1 && first.ends_with('}') {
+                return vec![InputFormat::JsonLines, InputFormat::Json, InputFormat::Json5];
+            }
+            return vec![InputFormat::Json, InputFormat::Json5, InputFormat::JsonLines];
+        }
+        
+        if let Some(after_bracket) = first.strip_prefix('[') {
+            // A TOON root table such as `[3]{a,b}:` or `[2]:`
+            let is_toon_header = after_bracket
+                .split_once(']')
+                .is_some_and(|(count, rest)| {
+                    count.trim().parse::<usize>().is_ok()
+                        && (rest.starts_with('{') || rest.starts_with(':'))
+                });
+            if is_toon_header {
+                return vec![InputFormat::Toon, InputFormat::Json];
+            }
+            
+            // TOML section headers: `[section]` or `[[array]]`
+            let is_toml_header = first.ends_with(']')
+                && first.trim_matches(|c| c == '[' || c == ']')
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '"'));
+            if is_toml_header && lines.len() > 1 {
+                return vec![InputFormat::Toml, InputFormat::Json, InputFormat::Json5];
+            }
+            return vec![InputFormat::Json, InputFormat::Json5, InputFormat::Toml];
+        }
+        
+        let looks_like = |sep: char| first.split_once(sep).is_some_and(|(key, _)| {
+            let key = key.trim().trim_matches('"');
+            !key.is_empty() && !key.contains(char::is_whitespace)
+        });
+        
+        if first.contains('\t') && !first.contains(':') {
+            return vec![InputFormat::Tsv, InputFormat::Toon];
+        }
+        if looks_like('=') && !looks_like(':') {
+            return vec![InputFormat::Toml, InputFormat::Toon];
+        }
+        if first.contains(',') && !first.contains(':') && !first.contains('=') {
+            return vec![InputFormat::Csv, InputFormat::Toon];
         }
+        
+        vec![InputFormat::Toon, InputFormat::Toml]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(Parser::detect_format("{\"a\": 1}"), InputFormat::Json);
+        assert_eq!(Parser::detect_format("// fixture\n{a: 1}"), InputFormat::Json5);
+        assert_eq!(Parser::detect_format("/* fixture */ [1]"), InputFormat::Json5);
+        assert_eq!(Parser::detect_format("name: \"x\""), InputFormat::Toon);
+        assert_eq!(Parser::detect_format("[2]:\n  - id: 1\n  - id: 2"), InputFormat::Toon);
+        assert_eq!(Parser::detect_format("[[tools]]\nname = \"a\""), InputFormat::Toml);
+        assert_eq!(Parser::detect_format("{\"a\": 1}\n{\"a\": 2}"), InputFormat::JsonLines);
+        assert_eq!(Parser::detect_format("id,name\n1,Alice"), InputFormat::Csv);
+    }
+
+    #[test]
+    fn test_detect_agreeing_extension_is_high_confidence() {
+        let detection = Parser::detect("{\"a\": 1}", Some("json"));
+        assert_eq!(detection.format, InputFormat::Json);
+        assert_eq!(detection.confidence, Confidence::High);
+        assert!(!detection.is_conflict());
+        
+        let detection = Parser::detect("// comment\n{a: 1}", Some("json"));
+        assert_eq!(detection.format, InputFormat::Json5);
+        assert!(!detection.is_conflict());
+    }
+
+    #[test]
+    fn test_detect_conflicting_extension() {
+        let detection = Parser::detect("name: \"x\"\nage: 3", Some("json"));
+        assert_eq!(detection.format, InputFormat::Toon);
+        assert_eq!(detection.extension_format, Some(InputFormat::Json));
+        assert_eq!(detection.confidence, Confidence::Medium);
+        assert!(detection.is_conflict());
+    }
+
+    #[test]
+    fn test_detect_unparseable_content_is_low_confidence() {
+        let detection = Parser::detect("{\"a\": ", Some("json"));
+        assert_eq!(detection.format, InputFormat::Json);
+        assert_eq!(detection.confidence, Confidence::Low);
     }
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+//! Helpers for the value paths used in diagnostics, e.g. `users[1].name`.
+
+/// Appends an object key to a path; the root path is empty.
+pub fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Appends an array or table row index to a path.
+pub fn join_index(path: &str, index: usize) -> String {
+    format!("{}[{}]", path, index)
+}
+
+/// Returns the parent of a path, or `None` for the root.
+pub fn parent(path: &str) -> Option<&str> {
+    if path.is_empty() {
+        return None;
+    }
+    if let Some(stripped) = path.strip_suffix(']') {
+        if let Some(pos) = stripped.rfind('[') {
+            return Some(&path[..pos]);
+        }
+    }
+    Some(path.rfind('.').map_or("", |pos| &path[..pos]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_and_parent() {
+        let row = join_index(&join_key("", "users"), 1);
+        let field = join_key(&row, "name");
+        
+        assert_eq!(field, "users[1].name");
+        assert_eq!(parent(&field), Some("users[1]"));
+        assert_eq!(parent("users[1]"), Some("users"));
+        assert_eq!(parent("users"), Some(""));
+        assert_eq!(parent(""), None);
+    }
+}
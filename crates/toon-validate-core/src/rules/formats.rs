@@ -0,0 +1,121 @@
+use super::{Node, Rule, RuleContext};
+use crate::{diagnostic::codes, format::StringFormat, path, value::Value};
+
+/// Warns about strings that break a format most of their table column
+/// shares, such as `"yesterday"` in a column of timestamps.
+pub struct ColumnFormats;
+
+impl ColumnFormats {
+    /// The format shared by more than half of a column's strings, when at
+    /// least two of them have it. One odd value out of two is no pattern.
+    fn infer_format<'a>(strings: impl Iterator<Item = &'a str>) -> Option<StringFormat> {
+        let mut counts: Vec<(StringFormat, usize)> = Vec::new();
+        let mut total = 0;
+        for s in strings {
+            total += 1;
+            let Some(format) = StringFormat::detect(s) else {
+                continue;
+            };
+            match counts.iter_mut().find(|(f, _)| *f == format) {
+                Some(entry) => entry.1 += 1,
+                None => counts.push((format, 1)),
+            }
+        }
+    
+        counts
+            .into_iter()
+            .find(|(_, count)| *count >= 2 && *count * 2 > total)
+            .map(|(format, _)| format)
+    }
+}
+
+impl Rule for ColumnFormats {
+    fn id(&self) -> &'static str {
+        "column-format"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::TABLE_COLUMN_FORMAT_MISMATCH
+    }
+    
+    fn description(&self) -> &'static str {
+        "Strings in a table column should share the column's format"
+    }
+    
+    fn check(&self, node: &Node, ctx: &mut RuleContext) {
+        let Value::Table(table) = node.value else {
+            return;
+        };
+        let mut columns: Vec<&String> = table.rows.iter().flat_map(|row| row.keys()).collect();
+        columns.sort();
+        columns.dedup();
+    
+        for column in columns {
+            let strings: Vec<(usize, &str)> = table
+                .rows
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, row)| match row.get(column) {
+                    Some(Value::String(s)) => Some((idx, s.as_str())),
+                    _ => None,
+                })
+                .collect();
+            let Some(format) = Self::infer_format(strings.iter().map(|(_, s)| *s)) else {
+                continue;
+            };
+    
+            for (idx, s) in strings {
+                if !format.matches(s) {
+                    ctx.report(
+                        &path::join_key(&path::join_index(node.path, idx), column),
+                        format!(
+                            "Table {} column {} row {}: expected a {} string like the rest of the column, found {:?}",
+                            table.name,
+                            column,
+                            idx + 1,
+                            format,
+                            s
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagnostic::Severity, rules::RuleRegistry, value::Table};
+    use std::collections::HashMap;
+
+    fn stamps(values: &[&str]) -> Value {
+        Value::Table(Table {
+            name: "events".to_string(),
+            declared_rows: values.len(),
+            rows: values
+                .iter()
+                .map(|v| HashMap::from([("created_at".to_string(), Value::String(v.to_string()))]))
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn test_reports_format_outliers() {
+        let registry = RuleRegistry::with_defaults();
+        let diagnostics = registry.run(&stamps(&["2024-01-05T10:00:00Z", "2024-01-06T11:30:00Z", "yesterday"]));
+    
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, codes::TABLE_COLUMN_FORMAT_MISMATCH);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].path, "[2].created_at");
+        assert_eq!(
+            diagnostics[0].message,
+            "Table events column created_at row 3: expected a date-time string like the rest of the column, found \"yesterday\""
+        );
+    
+        // Half a column isn't a pattern, and free text isn't a URI
+        assert!(registry.run(&stamps(&["2024-01-05", "2024-01-06", "soon", "later"])).is_empty());
+        assert!(registry.run(&stamps(&["TODO:fix", "Re:meeting", "plain note"])).is_empty());
+    }
+}
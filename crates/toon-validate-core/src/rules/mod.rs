@@ -0,0 +1,433 @@
+//! Lint rules run over a parsed document.
+//!
+//! A [`Rule`] inspects each [`Node`] of the document (and optionally the whole
+//! document once) and reports findings through a [`RuleContext`]. Rules are
+//! collected in a [`RuleRegistry`], which also holds the level each rule runs at.
+
+mod formats;
+mod naming;
+mod references;
+mod scalars;
+mod structure;
+mod unique;
+mod waste;
+
+pub use formats::ColumnFormats;
+pub use naming::Naming;
+pub use references::{DanglingReference, ForeignKey, ForeignKeys};
+pub(crate) use references::parse_foreign_keys;
+pub use scalars::AmbiguousScalars;
+pub use structure::{RepeatedKeys, EmptyArray, EmptyObject, EmptyTable};
+pub use unique::UniqueKeys;
+pub use waste::{ConstantColumns, DerivedColumns, DuplicateRows, SparseColumns};
+pub(crate) use unique::{parse_key, parse_key_list};
+
+use crate::{
+    diagnostic::{Diagnostic, Fix, Severity},
+    error::{Result, TqError},
+    path,
+    source_map::SourceMap,
+    value::{Table, Value},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Options passed to [`Rule::configure`], e.g. from a config file.
+pub type RuleOptions = serde_json::Map<String, serde_json::Value>;
+
+/// How a rule's findings are reported, or `Off` to disable it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Info,
+    #[serde(alias = "warning")]
+    Warn,
+    Error,
+}
+
+impl RuleLevel {
+    /// The severity of diagnostics at this level; `None` when the rule is off.
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            RuleLevel::Off => None,
+            RuleLevel::Info => Some(Severity::Info),
+            RuleLevel::Warn => Some(Severity::Warning),
+            RuleLevel::Error => Some(Severity::Error),
+        }
+    }
+}
+
+impl FromStr for RuleLevel {
+    type Err = TqError;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(RuleLevel::Off),
+            "info" => Ok(RuleLevel::Info),
+            "warn" | "warning" => Ok(RuleLevel::Warn),
+            "error" => Ok(RuleLevel::Error),
+            other => Err(TqError::InvalidConfig(format!(
+                "unknown rule level '{}', expected off, info, warn or error",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for RuleLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleLevel::Off => write!(f, "off"),
+            RuleLevel::Info => write!(f, "info"),
+            RuleLevel::Warn => write!(f, "warn"),
+            RuleLevel::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A `<rule-id>=<level>` setting, as given to `tval check --rule`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleOverride {
+    pub id: String,
+    pub level: RuleLevel,
+}
+
+impl FromStr for RuleOverride {
+    type Err = TqError;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        let (id, level) = s.split_once('=').ok_or_else(|| {
+            TqError::InvalidConfig(format!("expected <rule>=<level>, got '{}'", s))
+        })?;
+        Ok(RuleOverride {
+            id: id.trim().to_string(),
+            level: level.trim().parse()?,
+        })
+    }
+}
+
+/// A value in the document together with where it sits.
+#[derive(Debug, Clone, Copy)]
+pub struct Node<'a> {
+    /// Path of the value, e.g. `users[1].name`; empty for the root
+    pub path: &'a str,
+    /// Object key or table column the value is stored under
+    pub key: Option<&'a str>,
+    pub value: &'a Value,
+    /// The table whose row holds this value, for table cells
+    pub table: Option<&'a Table>,
+}
+
+/// Visits every value in the document depth-first, in document order with
+/// object keys and table columns sorted.
+pub fn walk(value: &Value, visit: &mut dyn FnMut(&Node)) {
+    walk_node(value, "", None, None, visit);
+}
+
+fn walk_node<'a>(
+    value: &'a Value,
+    path: &str,
+    key: Option<&'a str>,
+    table: Option<&'a Table>,
+    visit: &mut dyn FnMut(&Node),
+) {
+    visit(&Node { path, key, value, table });
+    
+    match value {
+        Value::Object(obj) => {
+            let mut keys: Vec<&String> = obj.keys().collect();
+            keys.sort();
+            for key in keys {
+                walk_node(&obj[key], &path::join_key(path, key), Some(key), None, visit);
+            }
+        }
+        Value::Array(arr) => {
+            for (idx, item) in arr.iter().enumerate() {
+                walk_node(item, &path::join_index(path, idx), None, None, visit);
+            }
+        }
+        Value::Table(t) => {
+            for (idx, row) in t.rows.iter().enumerate() {
+                let row_path = path::join_index(path, idx);
+                let mut columns: Vec<&String> = row.keys().collect();
+                columns.sort();
+                for column in columns {
+                    let cell_path = path::join_key(&row_path, column);
+                    walk_node(&row[column], &cell_path, Some(column), Some(t), visit);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Where a rule reports its findings.
+pub struct RuleContext<'a> {
+    rule: &'a dyn Rule,
+    severity: Severity,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl<'a> RuleContext<'a> {
+    /// The severity this rule is running at.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+    
+    /// Records a finding at `path` with the rule's code and configured severity.
+    /// The returned diagnostic can be extended, e.g. with a fix.
+    pub fn report(&mut self, path: &str, message: impl Into<String>) -> &mut Diagnostic {
+        let diagnostic = Diagnostic::new(self.severity, self.rule.code(), path, message)
+            .with_rule(self.rule.id());
+        self.diagnostics.push(diagnostic);
+        self.diagnostics.last_mut().unwrap()
+    }
+}
+
+pub trait Rule {
+    /// Stable kebab-case ID used on the command line and in configuration.
+    fn id(&self) -> &'static str;
+    
+    /// Diagnostic code for this rule's findings.
+    fn code(&self) -> &'static str;
+    
+    fn description(&self) -> &'static str;
+    
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Warn
+    }
+    
+    /// Applies rule-specific options. Rules without options reject any.
+    fn configure(&mut self, options: &RuleOptions) -> Result<()> {
+        match options.keys().next() {
+            Some(key) => Err(TqError::InvalidConfig(format!(
+                "rule {} has no option '{}'",
+                self.id(),
+                key
+            ))),
+            None => Ok(()),
+        }
+    }
+    
+    /// Called for every value in the document.
+    fn check(&self, _node: &Node, _ctx: &mut RuleContext) {}
+    
+    /// Called once with the root value, after all nodes have been visited.
+    fn check_document(&self, _root: &Value, _ctx: &mut RuleContext) {}
+    
+    /// Called with the parser's source map, for rules about how the document
+    /// was written rather than what it holds. See [`RuleRegistry::run_source`].
+    fn check_source(&self, _source_map: &SourceMap, _ctx: &mut RuleContext) {}
+    
+    /// A fix for one of this rule's diagnostics, given the document and the
+    /// source it was parsed from, or `None` when no edit is safe.
+    /// See [`RuleRegistry::suggest_fixes`].
+    fn fix(&self, _diagnostic: &Diagnostic, _value: &Value, _source: &str, _source_map: &SourceMap) -> Option<Fix> {
+        None
+    }
+}
+
+struct RegisteredRule {
+    rule: Box<dyn Rule>,
+    level: RuleLevel,
+}
+
+/// The set of rules to run and the level of each.
+#[derive(Default)]
+pub struct RuleRegistry {
+    rules: Vec<RegisteredRule>,
+}
+
+impl RuleRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// A registry holding the built-in rules at their default levels.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(ColumnFormats);
+        registry.register(EmptyObject);
+        registry.register(EmptyArray);
+        registry.register(EmptyTable);
+        registry.register(UniqueKeys::default());
+        registry.register(ForeignKeys::default());
+        registry.register(AmbiguousScalars::default());
+        registry.register(Naming::default());
+        registry.register(RepeatedKeys);
+        registry.register(ConstantColumns::default());
+        registry.register(SparseColumns::default());
+        registry.register(DuplicateRows);
+        registry.register(DerivedColumns::default());
+        registry
+    }
+    
+    /// Adds a rule at its default level, replacing any rule with the same ID.
+    pub fn register(&mut self, rule: impl Rule + 'static) {
+        let registered = RegisteredRule {
+            level: rule.default_level(),
+            rule: Box::new(rule),
+        };
+        match self.rules.iter_mut().find(|r| r.rule.id() == registered.rule.id()) {
+            Some(existing) => *existing = registered,
+            None => self.rules.push(registered),
+        }
+    }
+    
+    pub fn level(&self, id: &str) -> Option<RuleLevel> {
+        self.rules.iter().find(|r| r.rule.id() == id).map(|r| r.level)
+    }
+    
+    pub fn set_level(&mut self, id: &str, level: RuleLevel) -> Result<()> {
+        self.find_mut(id)?.level = level;
+        Ok(())
+    }
+    
+    pub fn apply_override(&mut self, rule_override: &RuleOverride) -> Result<()> {
+        self.set_level(&rule_override.id, rule_override.level)
+    }
+    
+    pub fn configure(&mut self, id: &str, options: &RuleOptions) -> Result<()> {
+        self.find_mut(id)?.rule.configure(options)
+    }
+    
+    /// The registered rules with their current levels, in registration order.
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, RuleLevel)> {
+        self.rules.iter().map(|r| (r.rule.as_ref(), r.level))
+    }
+    
+    /// Runs every enabled rule over the document.
+    pub fn run(&self, value: &Value) -> Vec<Diagnostic> {
+        let active = self.active();
+        
+        let mut diagnostics = Vec::new();
+        walk(value, &mut |node| {
+            for &(rule, severity) in &active {
+                let mut ctx = RuleContext { rule, severity, diagnostics: &mut diagnostics };
+                rule.check(node, &mut ctx);
+            }
+        });
+        for &(rule, severity) in &active {
+            let mut ctx = RuleContext { rule, severity, diagnostics: &mut diagnostics };
+            rule.check_document(value, &mut ctx);
+        }
+        diagnostics
+    }
+    
+    /// Runs every enabled rule's source checks. Separate from `run` because
+    /// only some parsers produce a source map.
+    pub fn run_source(&self, source_map: &SourceMap) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (rule, severity) in self.active() {
+            let mut ctx = RuleContext { rule, severity, diagnostics: &mut diagnostics };
+            rule.check_source(source_map, &mut ctx);
+        }
+        diagnostics
+    }
+    
+    /// Asks the rule behind each diagnostic without a fix for one. Needs the
+    /// source text, so in practice only TOON input gets these fixes.
+    pub fn suggest_fixes(&self, diagnostics: &mut [Diagnostic], value: &Value, source: &str, source_map: &SourceMap) {
+        for diagnostic in diagnostics.iter_mut().filter(|d| d.fix.is_none()) {
+            let Some(registered) = self.rules.iter().find(|r| diagnostic.rule.as_deref() == Some(r.rule.id())) else {
+                continue;
+            };
+            diagnostic.fix = registered.rule.fix(diagnostic, value, source, source_map);
+        }
+    }
+    
+    fn active(&self) -> Vec<(&dyn Rule, Severity)> {
+        self.rules
+            .iter()
+            .filter_map(|r| Some((r.rule.as_ref(), r.level.severity()?)))
+            .collect()
+    }
+    
+    fn find_mut(&mut self, id: &str) -> Result<&mut RegisteredRule> {
+        self.rules
+            .iter_mut()
+            .find(|r| r.rule.id() == id)
+            .ok_or_else(|| TqError::InvalidConfig(format!("unknown rule '{}'", id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct NoTodo;
+
+    impl Rule for NoTodo {
+        fn id(&self) -> &'static str {
+            "no-todo"
+        }
+        
+        fn code(&self) -> &'static str {
+            "X0001"
+        }
+        
+        fn description(&self) -> &'static str {
+            "Strings must not contain TODO"
+        }
+        
+        fn check(&self, node: &Node, ctx: &mut RuleContext) {
+            if let Value::String(s) = node.value {
+                if s.contains("TODO") {
+                    ctx.report(node.path, "Contains TODO");
+                }
+            }
+        }
+    }
+
+    fn sample() -> Value {
+        let mut obj = HashMap::new();
+        obj.insert("note".to_string(), Value::String("TODO later".to_string()));
+        obj.insert("tags".to_string(), Value::Array(vec![]));
+        Value::Object(obj)
+    }
+
+    #[test]
+    fn test_walk_visits_paths_in_order() {
+        let mut paths = Vec::new();
+        walk(&sample(), &mut |node| paths.push(node.path.to_string()));
+        assert_eq!(paths, vec!["", "note", "tags"]);
+    }
+
+    #[test]
+    fn test_custom_rule_and_levels() {
+        let mut registry = RuleRegistry::with_defaults();
+        registry.register(NoTodo);
+        
+        let diagnostics = registry.run(&sample());
+        let found: Vec<(&str, Option<&str>, Severity)> = diagnostics
+            .iter()
+            .map(|d| (d.path.as_str(), d.rule.as_deref(), d.severity))
+            .collect();
+        assert_eq!(found, vec![
+            ("note", Some("no-todo"), Severity::Warning),
+            ("tags", Some("empty-array"), Severity::Warning),
+        ]);
+        
+        registry.apply_override(&"no-todo=error".parse().unwrap()).unwrap();
+        registry.set_level("empty-array", RuleLevel::Off).unwrap();
+        let diagnostics = registry.run(&sample());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "X0001");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_unknown_rule_and_options_are_rejected() {
+        let mut registry = RuleRegistry::with_defaults();
+        assert!(registry.set_level("no-such-rule", RuleLevel::Warn).is_err());
+        assert!("empty-array=loud".parse::<RuleOverride>().is_err());
+        
+        let mut options = RuleOptions::new();
+        options.insert("max".to_string(), serde_json::json!(3));
+        assert!(registry.configure("empty-array", &options).is_err());
+    }
+}
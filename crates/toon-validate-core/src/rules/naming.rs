@@ -0,0 +1,385 @@
+use super::{walk, Rule, RuleContext, RuleOptions};
+use crate::{
+    diagnostic::{codes, Diagnostic, Fix, FixSafety, Span, TextEdit},
+    error::{Result, TqError},
+    path,
+    source_map::SourceMap,
+    value::Value,
+};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Case {
+    Snake,
+    Camel,
+    Kebab,
+}
+
+impl Case {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "snake" | "snake_case" => Some(Case::Snake),
+            "camel" | "camelCase" => Some(Case::Camel),
+            "kebab" | "kebab-case" => Some(Case::Kebab),
+            _ => None,
+        }
+    }
+    
+    fn name(&self) -> &'static str {
+        match self {
+            Case::Snake => "snake_case",
+            Case::Camel => "camelCase",
+            Case::Kebab => "kebab-case",
+        }
+    }
+    
+    fn matches(&self, name: &str) -> bool {
+        let lower_word = |word: &str| {
+            !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        };
+        if !name.starts_with(|c: char| c.is_ascii_lowercase()) {
+            return false;
+        }
+        match self {
+            Case::Snake => name.split('_').all(lower_word),
+            Case::Kebab => name.split('-').all(lower_word),
+            Case::Camel => name.chars().all(|c| c.is_ascii_alphanumeric()),
+        }
+    }
+    
+    fn convert(&self, name: &str) -> String {
+        let words = words(name);
+        match self {
+            Case::Snake => words.join("_"),
+            Case::Kebab => words.join("-"),
+            Case::Camel => {
+                let mut converted = String::new();
+                for (idx, word) in words.iter().enumerate() {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) if idx > 0 => converted.extend(first.to_uppercase().chain(chars)),
+                        _ => converted.push_str(word),
+                    }
+                }
+                converted
+            }
+        }
+    }
+}
+
+/// Splits a name into lowercase words at separators and case changes, so
+/// `userID`, `user_id` and `User-Id` all become `user`, `id`.
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    
+    for (idx, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let prev = idx.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(idx + 1);
+        // `userName` splits before `N`, `HTTPServer` before `S`
+        let boundary = c.is_uppercase()
+            && prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()
+                || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase())));
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn looks_plural(name: &str) -> bool {
+    let last = words(name).pop().unwrap_or_default();
+    last.ends_with('s') || ["people", "children", "data", "media"].contains(&last.as_str())
+}
+
+/// The path with row and item indices dropped, e.g. `users[].name`, so a key
+/// repeated in every row counts once.
+fn without_indices(path: &str) -> String {
+    let mut template = String::new();
+    let mut in_index = false;
+    for c in path.chars() {
+        match c {
+            '[' => {
+                in_index = true;
+                template.push_str("[]");
+            }
+            ']' if in_index => in_index = false,
+            _ if in_index => {}
+            _ => template.push(c),
+        }
+    }
+    template
+}
+
+/// Checks keys, table names and table columns against naming conventions: a
+/// case (`snake`, `camel` or `kebab`), a maximum length, characters that
+/// must not appear, and plural table names. Does nothing until configured.
+/// Offers to rename keys to the configured case where that can't clash with
+/// an existing key.
+#[derive(Default)]
+pub struct Naming {
+    case: Option<Case>,
+    max_length: Option<usize>,
+    forbidden: Vec<char>,
+    plural_tables: bool,
+}
+
+impl Naming {
+    /// What's wrong with `name`, phrased to follow it.
+    fn problems(&self, name: &str, is_table: bool) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Some(case) = self.case {
+            if !case.matches(name.trim_start_matches('_')) {
+                match self.rename(name) {
+                    Some(renamed) => problems.push(format!("is not {} ({})", case.name(), renamed)),
+                    None => problems.push(format!("is not {}", case.name())),
+                }
+            }
+        }
+        if let Some(max) = self.max_length.filter(|max| name.chars().count() > *max) {
+            problems.push(format!("is longer than {} characters", max));
+        }
+        let mut forbidden: Vec<char> = Vec::new();
+        for c in name.chars().filter(|c| self.forbidden.contains(c)) {
+            if !forbidden.contains(&c) {
+                forbidden.push(c);
+            }
+        }
+        if !forbidden.is_empty() {
+            let listed: Vec<String> = forbidden.iter().map(|c| format!("'{}'", c)).collect();
+            problems.push(format!("contains {}", listed.join(", ")));
+        }
+        if is_table && self.plural_tables && !looks_plural(name) {
+            problems.push("should be plural".to_string());
+        }
+        problems
+    }
+    
+    /// The name in the configured case. Leading underscores, as in `_id`,
+    /// are kept.
+    fn rename(&self, name: &str) -> Option<String> {
+        let rest = name.trim_start_matches('_');
+        let converted = self.case?.convert(rest);
+        if converted.is_empty() || converted == rest {
+            return None;
+        }
+        Some(format!("{}{}", &name[..name.len() - rest.len()], converted))
+    }
+}
+
+impl Rule for Naming {
+    fn id(&self) -> &'static str {
+        "naming"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::NAMING
+    }
+    
+    fn description(&self) -> &'static str {
+        "Keys, table names and columns must follow the naming conventions"
+    }
+    
+    fn configure(&mut self, options: &RuleOptions) -> Result<()> {
+        for (name, option) in options {
+            match name.as_str() {
+                "case" => {
+                    self.case = Some(option.as_str().and_then(Case::parse).ok_or_else(|| {
+                        TqError::InvalidConfig("naming: 'case' must be snake, camel or kebab".to_string())
+                    })?);
+                }
+                "max_length" => {
+                    self.max_length = Some(option.as_u64().ok_or_else(|| {
+                        TqError::InvalidConfig("naming: 'max_length' must be a number".to_string())
+                    })? as usize);
+                }
+                "forbidden" => {
+                    self.forbidden = option
+                        .as_str()
+                        .ok_or_else(|| {
+                            TqError::InvalidConfig("naming: 'forbidden' must be a string of characters".to_string())
+                        })?
+                        .chars()
+                        .collect();
+                }
+                "plural_tables" => {
+                    self.plural_tables = option.as_bool().ok_or_else(|| {
+                        TqError::InvalidConfig("naming: 'plural_tables' must be true or false".to_string())
+                    })?;
+                }
+                other => {
+                    return Err(TqError::InvalidConfig(format!("rule naming has no option '{}'", other)));
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    fn check_document(&self, root: &Value, ctx: &mut RuleContext) {
+        // Keys repeat in every row or array item; report each once
+        let mut seen = HashSet::new();
+        walk(root, &mut |node| {
+            let (kind, name) = match (node.value, node.key) {
+                (Value::Table(table), key) => ("Table", key.unwrap_or(&table.name)),
+                (_, Some(key)) if node.table.is_some() => ("Column", key),
+                (_, Some(key)) => ("Key", key),
+                _ => return,
+            };
+            if !seen.insert(without_indices(node.path)) {
+                return;
+            }
+            let problems = self.problems(name, kind == "Table");
+            if !problems.is_empty() {
+                ctx.report(node.path, format!("{} {} {}", kind, name, problems.join(" and ")));
+            }
+        });
+    }
+    
+    /// Renames the key wherever it repeats (every row of a table, every item
+    /// of an array), unless the new name is taken there, another key next to
+    /// it would be renamed to the same name, or the key isn't written plainly
+    /// at its recorded position.
+    fn fix(&self, diagnostic: &Diagnostic, value: &Value, source: &str, source_map: &SourceMap) -> Option<Fix> {
+        let template = without_indices(&diagnostic.path);
+        let parent = path::parent(&template)?;
+        let mut key = None;
+        let mut targets = Vec::new();
+        let mut siblings = HashSet::new();
+        walk(value, &mut |node| {
+            let Some(node_key) = node.key else {
+                return;
+            };
+            let node_template = without_indices(node.path);
+            if node_template == template {
+                key = Some(node_key.to_string());
+                targets.push(node.path.to_string());
+            } else if path::parent(&node_template) == Some(parent) {
+                siblings.insert(node_key.to_string());
+            }
+        });
+        let key = key?;
+        let renamed = self.rename(&key)?;
+        // Two keys becoming one would lose a value
+        if siblings.iter().any(|sibling| *sibling == renamed || self.rename(sibling).as_ref() == Some(&renamed)) {
+            return None;
+        }
+    
+        let lines: Vec<&str> = source.lines().collect();
+        let mut edits = Vec::new();
+        for target in targets {
+            let span = source_map.get(&target)?;
+            let line = lines.get(span.line - 1)?;
+            let written: String = line.chars().skip(span.column - 1).collect();
+            let after = written.strip_prefix(key.as_str())?;
+            if !after.trim_start().starts_with([':', '[']) {
+                return None;
+            }
+            edits.push(TextEdit {
+                start: span,
+                end: Span::new(span.line, span.column + key.chars().count()),
+                new_text: renamed.clone(),
+            });
+        }
+    
+        Some(Fix {
+            description: format!("Rename {} to {}", key, renamed),
+            edits,
+            safety: FixSafety::Suggested,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fix::apply_fixes, rules::RuleRegistry, toon_parser::ToonParser};
+
+    const INPUT: &str = r#"userName: "ada"
+_id: 1
+longDescription: "x"
+total$: 3
+person[2]:
+  - firstName: "Ada"
+    user_name: "a"
+  - firstName: "Bob"
+    user_name: "b"
+settings:
+  darkMode: true
+  dark_mode: false"#;
+
+    #[test]
+    fn test_cases_and_words() {
+        assert_eq!(words("HTTPServer_url-ID2x"), vec!["http", "server", "url", "id2x"]);
+        assert_eq!(Case::Snake.convert("userID"), "user_id");
+        assert_eq!(Case::Camel.convert("user_id"), "userId");
+        assert_eq!(Case::Kebab.convert("User Name"), "user-name");
+        assert!(Case::Snake.matches("user_id2"));
+        assert!(!Case::Snake.matches("user__id"));
+        assert!(Case::Camel.matches("userId"));
+        assert!(!Case::Kebab.matches("user_id"));
+        assert_eq!(without_indices("users[10].tags[0]"), "users[].tags[]");
+        assert!(looks_plural("order_items") && looks_plural("people") && !looks_plural("person"));
+    }
+
+    #[test]
+    fn test_reports_and_renames() {
+        let mut registry = RuleRegistry::with_defaults();
+        let (value, source_map) = ToonParser::parse_with_source_map(INPUT).unwrap();
+        assert!(registry.run(&value).is_empty());
+    
+        let options = serde_json::json!({"case": "snake", "plural_tables": true, "max_length": 9, "forbidden": "$"});
+        registry.configure("naming", options.as_object().unwrap()).unwrap();
+        let mut diagnostics = registry.run(&value);
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(messages, vec![
+            "[TV0307] longDescription: Key longDescription is not snake_case (long_description) and is longer than 9 characters",
+            "[TV0307] person: Table person should be plural",
+            "[TV0307] person[0].firstName: Column firstName is not snake_case (first_name)",
+            "[TV0307] settings.darkMode: Key darkMode is not snake_case (dark_mode)",
+            "[TV0307] total$: Key total$ is not snake_case (total) and contains '$'",
+            "[TV0307] userName: Key userName is not snake_case (user_name)",
+        ]);
+    
+        // darkMode can't become dark_mode, which is taken
+        registry.suggest_fixes(&mut diagnostics, &value, INPUT, &source_map);
+        let fixes: Vec<Option<&str>> = diagnostics
+            .iter()
+            .map(|d| d.fix.as_ref().map(|fix| fix.description.as_str()))
+            .collect();
+        assert_eq!(fixes, vec![
+            Some("Rename longDescription to long_description"),
+            None,
+            Some("Rename firstName to first_name"),
+            None,
+            Some("Rename total$ to total"),
+            Some("Rename userName to user_name"),
+        ]);
+    
+        let outcome = apply_fixes(INPUT, &diagnostics, FixSafety::Suggested);
+        assert_eq!(outcome.applied, 4);
+        assert!(outcome.text.starts_with("user_name: \"ada\"\n_id: 1\nlong_description: \"x\"\ntotal: 3\nperson[2]:\n  - first_name: \"Ada\""));
+        assert!(outcome.text.contains("  - first_name: \"Bob\""));
+    
+        // userName and user-name would both become user_name
+        let input = "userName: \"a\"\nuser-name: \"b\"";
+        let (value, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        let mut diagnostics = registry.run(&value);
+        assert_eq!(diagnostics.len(), 2);
+        registry.suggest_fixes(&mut diagnostics, &value, input, &source_map);
+        assert!(diagnostics.iter().all(|d| d.fix.is_none()));
+    
+        let bad = serde_json::json!({"case": "shouty"});
+        assert!(registry.configure("naming", bad.as_object().unwrap()).is_err());
+    }
+}
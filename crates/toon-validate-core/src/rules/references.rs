@@ -0,0 +1,304 @@
+use super::{Rule, RuleContext, RuleLevel, RuleOptions};
+use crate::{
+    diagnostic::codes,
+    error::{Result, TqError},
+    path,
+    value::Value,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// A column whose values must appear in a column of another table, e.g.
+/// `orders.user_id -> users.id`. Tables are named by table name or path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignKey {
+    pub table: String,
+    pub column: String,
+    pub target_table: String,
+    pub target_column: String,
+}
+
+/// A row whose foreign-key value matches no row of the referenced table.
+/// The row index is 0-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DanglingReference {
+    /// Path of the referencing cell, e.g. `orders[2].user_id`
+    pub path: String,
+    /// Path of the referencing table
+    pub table: String,
+    pub row: usize,
+    pub value: Value,
+}
+
+impl ForeignKey {
+    /// Parses a `table.column` reference pair; the column is whatever follows
+    /// the last `.`, so nested tables can be named by path.
+    pub fn parse(from: &str, to: &str) -> Option<Self> {
+        let (table, column) = split_column(from)?;
+        let (target_table, target_column) = split_column(to)?;
+        Some(ForeignKey { table, column, target_table, target_column })
+    }
+    
+    /// The referencing and referenced tables, in that order, that aren't in
+    /// the document. A misspelt table name would otherwise make every value
+    /// dangle, or none.
+    pub fn missing_tables(&self, document: &Value) -> Vec<&str> {
+        let tables = collect_tables(document);
+        let mut missing: Vec<&str> = [self.table.as_str(), self.target_table.as_str()]
+            .into_iter()
+            .filter(|name| !tables.iter().any(|table| table.is_named(name)))
+            .collect();
+        missing.dedup();
+        missing
+    }
+    
+    /// Finds every non-null value in the referencing column that has no match
+    /// in the referenced column. Missing cells and nulls are not references,
+    /// and nothing is checked when the referenced table is missing (see
+    /// [`ForeignKey::missing_tables`]).
+    pub fn dangling(&self, document: &Value) -> Vec<DanglingReference> {
+        let tables = collect_tables(document);
+        if !tables.iter().any(|table| table.is_named(&self.target_table)) {
+            return Vec::new();
+        }
+    
+        // Values aren't hashable (floats), so key on their JSON form
+        let targets: HashSet<String> = tables
+            .iter()
+            .filter(|table| table.is_named(&self.target_table))
+            .flat_map(|table| table.rows.iter().flatten())
+            .filter_map(|row| row.get(&self.target_column))
+            .map(|value| value.to_json().to_string())
+            .collect();
+    
+        let mut dangling = Vec::new();
+        for table in tables.iter().filter(|table| table.is_named(&self.table)) {
+            for (idx, row) in table.rows.iter().enumerate() {
+                let Some(value) = row.and_then(|row| row.get(&self.column)) else {
+                    continue;
+                };
+                if *value == Value::Null || targets.contains(&value.to_json().to_string()) {
+                    continue;
+                }
+                dangling.push(DanglingReference {
+                    path: path::join_key(&path::join_index(&table.path, idx), &self.column),
+                    table: table.path.clone(),
+                    row: idx,
+                    value: value.clone(),
+                });
+            }
+        }
+        dangling
+    }
+}
+
+impl fmt::Display for ForeignKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{} -> {}.{}", self.table, self.column, self.target_table, self.target_column)
+    }
+}
+
+impl DanglingReference {
+    /// Describes the reference with a 1-based row number, e.g.
+    /// `user_id 42 in orders row 3 matches no users.id`.
+    pub fn message(&self, foreign_key: &ForeignKey) -> String {
+        format!(
+            "{} {} in {} row {} matches no {}.{}",
+            foreign_key.column,
+            self.value.to_json(),
+            self.table,
+            self.row + 1,
+            foreign_key.target_table,
+            foreign_key.target_column
+        )
+    }
+}
+
+fn split_column(reference: &str) -> Option<(String, String)> {
+    let (table, column) = reference.trim().rsplit_once('.')?;
+    if table.is_empty() || column.is_empty() {
+        return None;
+    }
+    Some((table.to_string(), column.to_string()))
+}
+
+/// Parses a map of `"table.column": "table.column"` references.
+pub(crate) fn parse_foreign_keys(json: &serde_json::Value) -> Option<Vec<ForeignKey>> {
+    json.as_object()?
+        .iter()
+        .map(|(from, to)| ForeignKey::parse(from, to.as_str()?))
+        .collect()
+}
+
+/// A table or array of objects found in the document.
+struct RowSet<'a> {
+    path: String,
+    name: Option<&'a str>,
+    rows: Vec<Option<&'a HashMap<String, Value>>>,
+}
+
+impl RowSet<'_> {
+    fn is_named(&self, table: &str) -> bool {
+        self.path == table || self.name == Some(table)
+    }
+}
+
+fn collect_tables(document: &Value) -> Vec<RowSet<'_>> {
+    let mut tables = Vec::new();
+    collect_into(document, String::new(), &mut tables);
+    tables
+}
+
+fn collect_into<'a>(value: &'a Value, at: String, tables: &mut Vec<RowSet<'a>>) {
+    match value {
+        Value::Object(obj) => {
+            for (key, child) in obj {
+                collect_into(child, path::join_key(&at, key), tables);
+            }
+        }
+        Value::Array(arr) => {
+            for (idx, item) in arr.iter().enumerate() {
+                collect_into(item, path::join_index(&at, idx), tables);
+            }
+        }
+        Value::Table(table) => {
+            for (idx, row) in table.rows.iter().enumerate() {
+                for (column, cell) in row {
+                    collect_into(cell, path::join_key(&path::join_index(&at, idx), column), tables);
+                }
+            }
+        }
+        _ => {}
+    }
+    
+    let name = match value {
+        Value::Table(table) => Some(table.name.as_str()),
+        _ => None,
+    };
+    if let Some(rows) = value.object_rows() {
+        tables.push(RowSet { path: at, name, rows });
+    }
+}
+
+/// Reports values in a referencing column that match no row of the
+/// referenced table. References are declared as `"orders.user_id" =
+/// "users.id"`; without any declared the rule does nothing.
+#[derive(Default)]
+pub struct ForeignKeys {
+    references: Vec<ForeignKey>,
+}
+
+impl Rule for ForeignKeys {
+    fn id(&self) -> &'static str {
+        "foreign-keys"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::DANGLING_REFERENCE
+    }
+    
+    fn description(&self) -> &'static str {
+        "Declared references between tables must resolve"
+    }
+    
+    // References are only checked when declared, so a miss is a real error
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Error
+    }
+    
+    fn configure(&mut self, options: &RuleOptions) -> Result<()> {
+        for (name, option) in options {
+            match name.as_str() {
+                "references" => {
+                    let references = parse_foreign_keys(option).ok_or_else(|| {
+                        TqError::InvalidConfig(
+                            "foreign-keys: 'references' must map \"table.column\" to \"table.column\"".to_string(),
+                        )
+                    })?;
+                    self.references.extend(references);
+                }
+                other => {
+                    return Err(TqError::InvalidConfig(format!("rule foreign-keys has no option '{}'", other)));
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    fn check_document(&self, root: &Value, ctx: &mut RuleContext) {
+        for foreign_key in &self.references {
+            for table in foreign_key.missing_tables(root) {
+                ctx.report("", format!("Unknown table {} in reference {}", table, foreign_key));
+            }
+            for reference in foreign_key.dangling(root) {
+                ctx.report(&reference.path, format!("Dangling reference: {}", reference.message(foreign_key)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rules::RuleRegistry, toon_parser::ToonParser};
+
+    const SHOP: &str = r#"users[2]:
+  - id: 1
+  - id: 2
+orders[4]:
+  - id: 10
+    user_id: 1
+  - id: 11
+    user_id: 3
+  - id: 12
+    user_id: null
+  - id: 13
+    user_id: 2"#;
+
+    #[test]
+    fn test_finds_dangling_references() {
+        let foreign_key = ForeignKey::parse("orders.user_id", "users.id").unwrap();
+        let dangling = foreign_key.dangling(&ToonParser::parse(SHOP).unwrap());
+    
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].path, "orders[1].user_id");
+        assert_eq!(dangling[0].message(&foreign_key), "user_id 3 in orders row 2 matches no users.id");
+        assert!(ForeignKey::parse("orders", "users.id").is_none());
+    }
+
+    #[test]
+    fn test_missing_tables_are_not_dangling() {
+        let document = ToonParser::parse(SHOP).unwrap();
+        let misspelt = ForeignKey::parse("orders.user_id", "user.id").unwrap();
+        assert_eq!(misspelt.missing_tables(&document), vec!["user"]);
+        assert!(misspelt.dangling(&document).is_empty());
+    
+        let both = ForeignKey::parse("order.user_id", "user.id").unwrap();
+        assert_eq!(both.missing_tables(&document), vec!["order", "user"]);
+        let present = ForeignKey::parse("orders.user_id", "users.id").unwrap();
+        assert!(present.missing_tables(&document).is_empty());
+    }
+
+    #[test]
+    fn test_rule_reports_configured_references() {
+        let mut registry = RuleRegistry::with_defaults();
+        assert!(registry.run(&ToonParser::parse(SHOP).unwrap()).is_empty());
+    
+        let options = serde_json::json!({"references": {"orders.user_id": "users.id"}});
+        registry.configure("foreign-keys", options.as_object().unwrap()).unwrap();
+        let diagnostics = registry.run(&ToonParser::parse(SHOP).unwrap());
+    
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, codes::DANGLING_REFERENCE);
+        assert_eq!(diagnostics[0].message, "Dangling reference: user_id 3 in orders row 2 matches no users.id");
+    
+        let misspelt = serde_json::json!({"references": {"order.user_id": "users.id"}});
+        registry.configure("foreign-keys", misspelt.as_object().unwrap()).unwrap();
+        let diagnostics = registry.run(&ToonParser::parse(SHOP).unwrap());
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[1].message, "Unknown table order in reference order.user_id -> users.id");
+    
+        let bad = serde_json::json!({"references": {"orders": "users.id"}});
+        assert!(registry.configure("foreign-keys", bad.as_object().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use super::{Rule, RuleContext, RuleOptions};
+use crate::{
+    diagnostic::{codes, Fix, FixSafety, Span, TextEdit},
+    error::{Result, TqError},
+    source_map::{SourceMap, UnquotedScalar},
+    value::Value,
+};
+
+/// Warns about unquoted tokens that were read as numbers but were likely
+/// meant as strings: leading zeros (`0012`), digit runs too long for an
+/// exact number, version-like decimals (`1.10`) and `NaN`/`inf`. Offers to
+/// quote them. Works from the source map, so only TOON input is checked.
+pub struct AmbiguousScalars {
+    /// Numbers with more digits than this are reported
+    max_digits: usize,
+}
+
+impl Default for AmbiguousScalars {
+    fn default() -> Self {
+        // An f64 holds 15 significant decimal digits exactly
+        AmbiguousScalars { max_digits: 15 }
+    }
+}
+
+impl AmbiguousScalars {
+    /// Why the token is likely a string, or `None` if reading it as a
+    /// number loses nothing worth mentioning.
+    fn ambiguity(&self, text: &str) -> Option<String> {
+        let number: f64 = text.parse().ok()?;
+        if !number.is_finite() {
+            return Some("not a finite number".to_string());
+        }
+        
+        let mantissa = text
+            .trim_start_matches(['-', '+'])
+            .split(['e', 'E'])
+            .next()
+            .unwrap_or_default();
+        let mut chars = mantissa.chars();
+        if chars.next() == Some('0') && chars.next().is_some_and(|c| c.is_ascii_digit()) {
+            return Some("leading zeros are dropped".to_string());
+        }
+        
+        let digits = mantissa.chars().filter(|c| c.is_ascii_digit()).count();
+        if digits > self.max_digits {
+            return Some(format!("more than {} digits are not kept exactly", self.max_digits));
+        }
+        
+        if let Some((_, fraction)) = mantissa.split_once('.') {
+            if fraction.len() > 1 && fraction.ends_with('0') {
+                return Some("trailing zeros are dropped".to_string());
+            }
+        }
+        None
+    }
+}
+
+fn render(text: &str) -> String {
+    match text.parse::<f64>() {
+        Ok(number) if number.is_finite() => Value::Number(number).to_json().to_string(),
+        Ok(number) => number.to_string(),
+        Err(_) => text.to_string(),
+    }
+}
+
+fn quote_fix(scalar: &UnquotedScalar) -> Fix {
+    let end = Span::new(scalar.span.line, scalar.span.column + scalar.text.chars().count());
+    Fix {
+        description: format!("Quote {}", scalar.text),
+        edits: vec![TextEdit {
+            start: scalar.span,
+            end,
+            new_text: format!("\"{}\"", scalar.text),
+        }],
+        // The value becomes a string, which may not be what the author meant
+        safety: FixSafety::Suggested,
+    }
+}
+
+impl Rule for AmbiguousScalars {
+    fn id(&self) -> &'static str {
+        "ambiguous-scalar"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::AMBIGUOUS_SCALAR
+    }
+    
+    fn description(&self) -> &'static str {
+        "Unquoted values that look like strings should be quoted"
+    }
+    
+    fn configure(&mut self, options: &RuleOptions) -> Result<()> {
+        for (name, option) in options {
+            match name.as_str() {
+                "max_digits" => {
+                    self.max_digits = option.as_u64().ok_or_else(|| {
+                        TqError::InvalidConfig("ambiguous-scalar: 'max_digits' must be a number".to_string())
+                    })? as usize;
+                }
+                other => {
+                    return Err(TqError::InvalidConfig(format!("rule ambiguous-scalar has no option '{}'", other)));
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    fn check_source(&self, source_map: &SourceMap, ctx: &mut RuleContext) {
+        for scalar in source_map.scalars() {
+            if let Some(reason) = self.ambiguity(&scalar.text) {
+                let message = format!(
+                    "Unquoted {} is read as the number {} ({}); quote it to keep it a string",
+                    scalar.text,
+                    render(&scalar.text),
+                    reason
+                );
+                let diagnostic = ctx.report(&scalar.path, message);
+                diagnostic.span = Some(scalar.span);
+                diagnostic.fix = Some(quote_fix(scalar));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fix::apply_fixes, rules::RuleRegistry, toon_parser::ToonParser};
+
+    #[test]
+    fn test_flags_likely_strings() {
+        let rule = AmbiguousScalars::default();
+        
+        assert_eq!(rule.ambiguity("0012").as_deref(), Some("leading zeros are dropped"));
+        assert_eq!(rule.ambiguity("-007").as_deref(), Some("leading zeros are dropped"));
+        assert_eq!(rule.ambiguity("1.10").as_deref(), Some("trailing zeros are dropped"));
+        assert_eq!(rule.ambiguity("12345678901234567890").as_deref(), Some("more than 15 digits are not kept exactly"));
+        assert_eq!(rule.ambiguity("NaN").as_deref(), Some("not a finite number"));
+        for fine in ["0", "0.5", "12", "1.5", "2.0", "1e5", "true", "null"] {
+            assert_eq!(rule.ambiguity(fine), None, "{}", fine);
+        }
+    }
+
+    #[test]
+    fn test_reports_with_quote_fix() {
+        let input = "sku: 0012\nversion: 1.10\ncount: 3\ntags: [1, 007]";
+        let (_, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        let diagnostics = RuleRegistry::with_defaults().run_source(&source_map);
+        
+        let paths: Vec<&str> = diagnostics.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec!["sku", "version", "tags[1]"]);
+        assert_eq!(
+            diagnostics[0].message,
+            "Unquoted 0012 is read as the number 12 (leading zeros are dropped); quote it to keep it a string"
+        );
+        assert_eq!(apply_fixes(input, &diagnostics, FixSafety::Suggested).text, "sku: \"0012\"\nversion: \"1.10\"\ncount: 3\ntags: [1, \"007\"]");
+    }
+}
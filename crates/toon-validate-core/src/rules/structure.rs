@@ -0,0 +1,112 @@
+use super::{Node, Rule, RuleContext};
+use crate::{diagnostic::codes, source_map::SourceMap, value::Value};
+
+/// Warns about objects with no keys.
+pub struct EmptyObject;
+
+impl Rule for EmptyObject {
+    fn id(&self) -> &'static str {
+        "empty-object"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::EMPTY_OBJECT
+    }
+    
+    fn description(&self) -> &'static str {
+        "Objects should not be empty"
+    }
+    
+    fn check(&self, node: &Node, ctx: &mut RuleContext) {
+        if matches!(node.value, Value::Object(obj) if obj.is_empty()) {
+            ctx.report(node.path, "Empty object");
+        }
+    }
+}
+
+/// Warns about arrays with no items.
+pub struct EmptyArray;
+
+impl Rule for EmptyArray {
+    fn id(&self) -> &'static str {
+        "empty-array"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::EMPTY_ARRAY
+    }
+    
+    fn description(&self) -> &'static str {
+        "Arrays should not be empty"
+    }
+    
+    fn check(&self, node: &Node, ctx: &mut RuleContext) {
+        if matches!(node.value, Value::Array(arr) if arr.is_empty()) {
+            ctx.report(node.path, "Empty array");
+        }
+    }
+}
+
+/// Warns about tables with no rows, whether declared empty (`users[0]:`)
+/// or declaring rows they don't contain.
+pub struct EmptyTable;
+
+impl Rule for EmptyTable {
+    fn id(&self) -> &'static str {
+        "empty-table"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::EMPTY_TABLE
+    }
+    
+    fn description(&self) -> &'static str {
+        "Tables should contain rows"
+    }
+    
+    fn check(&self, node: &Node, ctx: &mut RuleContext) {
+        if let Value::Table(table) = node.value {
+            if table.rows.is_empty() && table.declared_rows > 0 {
+                ctx.report(
+                    node.path,
+                    format!("Table declared with {} rows but is empty", table.declared_rows),
+                );
+            } else if table.rows.is_empty() {
+                ctx.report(node.path, "Empty table");
+            }
+        }
+    }
+}
+
+/// Warns about keys written twice in one object or table row, of which only
+/// the last value is kept. Works from the source map, so only TOON input is
+/// checked.
+pub struct RepeatedKeys;
+
+impl Rule for RepeatedKeys {
+    fn id(&self) -> &'static str {
+        "repeated-key"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::REPEATED_KEY
+    }
+    
+    fn description(&self) -> &'static str {
+        "Objects and rows should not repeat a key"
+    }
+    
+    fn check_source(&self, source_map: &SourceMap, ctx: &mut RuleContext) {
+        for duplicate in source_map.duplicate_keys() {
+            let message = match duplicate.first {
+                Some(first) => format!(
+                    "Key {} repeats line {}; only the last value is kept",
+                    duplicate.key, first.line
+                ),
+                None => format!("Key {} is repeated; only the last value is kept", duplicate.key),
+            };
+            let diagnostic = ctx.report(&duplicate.path, message);
+            diagnostic.span = Some(duplicate.span);
+        }
+    }
+}
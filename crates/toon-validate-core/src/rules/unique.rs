@@ -0,0 +1,170 @@
+use super::{Node, Rule, RuleContext, RuleOptions};
+use crate::{
+    diagnostic::codes,
+    error::{Result, TqError},
+    path,
+    value::{find_duplicate_rows, Value},
+};
+use std::collections::BTreeMap;
+
+/// Reports rows that repeat a key. Keys are declared per table, by table
+/// name or path, and columns named in `auto_detect` (`id` by default) are
+/// checked in every table.
+pub struct UniqueKeys {
+    keys: BTreeMap<String, Vec<Vec<String>>>,
+    auto_detect: Vec<String>,
+}
+
+impl Default for UniqueKeys {
+    fn default() -> Self {
+        UniqueKeys {
+            keys: BTreeMap::new(),
+            auto_detect: vec!["id".to_string()],
+        }
+    }
+}
+
+/// Parses a list of keys, where each key is a column name or a list of
+/// column names forming a composite key.
+pub(crate) fn parse_key_list(json: &serde_json::Value) -> Option<Vec<Vec<String>>> {
+    json.as_array()?.iter().map(parse_key).collect()
+}
+
+/// Parses a single key: a column name or a list of column names.
+pub(crate) fn parse_key(json: &serde_json::Value) -> Option<Vec<String>> {
+    match json {
+        serde_json::Value::String(column) => Some(vec![column.clone()]),
+        serde_json::Value::Array(columns) if !columns.is_empty() => columns
+            .iter()
+            .map(|c| c.as_str().map(str::to_string))
+            .collect(),
+        _ => None,
+    }
+}
+
+impl Rule for UniqueKeys {
+    fn id(&self) -> &'static str {
+        "unique-keys"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::DUPLICATE_KEY
+    }
+    
+    fn description(&self) -> &'static str {
+        "Key columns must not repeat across table rows"
+    }
+    
+    fn configure(&mut self, options: &RuleOptions) -> Result<()> {
+        for (name, option) in options {
+            match name.as_str() {
+                "keys" => {
+                    let tables = option.as_object().ok_or_else(|| {
+                        TqError::InvalidConfig("unique-keys: 'keys' must map tables to keys".to_string())
+                    })?;
+                    for (table, keys) in tables {
+                        let keys = parse_key_list(keys).ok_or_else(|| {
+                            TqError::InvalidConfig(format!(
+                                "unique-keys: keys for '{}' must be a list of columns or column lists",
+                                table
+                            ))
+                        })?;
+                        self.keys.insert(table.clone(), keys);
+                    }
+                }
+                "auto_detect" => {
+                    self.auto_detect = option
+                        .as_array()
+                        .and_then(|columns| columns.iter().map(|c| c.as_str().map(str::to_string)).collect())
+                        .ok_or_else(|| {
+                            TqError::InvalidConfig("unique-keys: 'auto_detect' must be a list of columns".to_string())
+                        })?;
+                }
+                other => {
+                    return Err(TqError::InvalidConfig(format!("rule unique-keys has no option '{}'", other)));
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    fn check(&self, node: &Node, ctx: &mut RuleContext) {
+        let name = match node.value {
+            Value::Table(table) => Some(table.name.as_str()),
+            Value::Array(_) => None,
+            _ => return,
+        };
+        let Some(rows) = node.value.object_rows() else {
+            return;
+        };
+        
+        let mut keys: Vec<Vec<String>> = self
+            .keys
+            .get(node.path)
+            .or_else(|| name.and_then(|name| self.keys.get(name)))
+            .cloned()
+            .unwrap_or_default();
+        for column in &self.auto_detect {
+            let present = rows.iter().flatten().any(|row| row.contains_key(column));
+            let key = vec![column.clone()];
+            if present && !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        
+        for key in &keys {
+            for duplicate in find_duplicate_rows(rows.iter().copied(), key) {
+                ctx.report(&path::join_index(node.path, duplicate.duplicate), capitalize(&duplicate.message(key)));
+            }
+        }
+    }
+}
+
+fn capitalize(message: &str) -> String {
+    let mut chars = message.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rules::RuleRegistry, toon_parser::ToonParser};
+
+    const ORDERS: &str = r#"orders[4]:
+  - id: 1
+    sku: "a"
+    line: 1
+  - id: 2
+    sku: "b"
+    line: 1
+  - id: 1
+    sku: "a"
+    line: 2
+  - id: 3
+    sku: "a"
+    line: 2"#;
+
+    #[test]
+    fn test_auto_detects_id() {
+        let registry = RuleRegistry::with_defaults();
+        let diagnostics = registry.run(&ToonParser::parse(ORDERS).unwrap());
+        
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "orders[2]");
+        assert_eq!(diagnostics[0].message, "Duplicate id 1 in rows 1 and 3");
+    }
+
+    #[test]
+    fn test_declared_composite_keys() {
+        let mut registry = RuleRegistry::with_defaults();
+        let options = serde_json::json!({"keys": {"orders": [["sku", "line"]]}, "auto_detect": []});
+        registry.configure("unique-keys", options.as_object().unwrap()).unwrap();
+        let diagnostics = registry.run(&ToonParser::parse(ORDERS).unwrap());
+        
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "orders[3]");
+        assert_eq!(diagnostics[0].message, "Duplicate (sku, line) (\"a\", 2) in rows 3 and 4");
+    }
+}
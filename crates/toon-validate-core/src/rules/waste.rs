@@ -0,0 +1,437 @@
+//! Rules for table content that costs tokens without adding information.
+//! Each finding estimates the tokens a rewrite would save.
+
+use super::{Node, Rule, RuleContext, RuleLevel, RuleOptions};
+use crate::{
+    diagnostic::codes,
+    error::{Result, TqError},
+    path,
+    token_estimator::TokenEstimator,
+    value::{Table, Value},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Every column of the table, sorted.
+fn columns(table: &Table) -> Vec<&String> {
+    let mut columns: Vec<&String> = table.rows.iter().flat_map(|row| row.keys()).collect();
+    columns.sort();
+    columns.dedup();
+    columns
+}
+
+fn cell_path(table_path: &str, row: usize, column: &str) -> String {
+    path::join_key(&path::join_index(table_path, row), column)
+}
+
+/// The column's fields in every row that has it.
+fn fields<'a>(table: &'a Table, column: &str) -> Vec<(&'a String, &'a Value)> {
+    table.rows.iter().filter_map(|row| row.get_key_value(column)).collect()
+}
+
+fn min_rows_option(rule: &str, option: &serde_json::Value) -> Result<usize> {
+    option
+        .as_u64()
+        .map(|n| n as usize)
+        .ok_or_else(|| TqError::InvalidConfig(format!("{}: 'min_rows' must be a number", rule)))
+}
+
+fn unknown_option(rule: &str, option: &str) -> TqError {
+    TqError::InvalidConfig(format!("rule {} has no option '{}'", rule, option))
+}
+
+/// Notes table columns that hold the same non-null value in every row.
+/// Tables with fewer than `min_rows` rows (default 3) are skipped.
+pub struct ConstantColumns {
+    min_rows: usize,
+}
+
+impl Default for ConstantColumns {
+    fn default() -> Self {
+        ConstantColumns { min_rows: 3 }
+    }
+}
+
+impl Rule for ConstantColumns {
+    fn id(&self) -> &'static str {
+        "constant-column"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::CONSTANT_COLUMN
+    }
+    
+    fn description(&self) -> &'static str {
+        "Table columns should not repeat one value in every row"
+    }
+    
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Info
+    }
+    
+    fn configure(&mut self, options: &RuleOptions) -> Result<()> {
+        for (name, option) in options {
+            match name.as_str() {
+                "min_rows" => self.min_rows = min_rows_option(self.id(), option)?,
+                other => return Err(unknown_option(self.id(), other)),
+            }
+        }
+        Ok(())
+    }
+    
+    fn check(&self, node: &Node, ctx: &mut RuleContext) {
+        let Value::Table(table) = node.value else {
+            return;
+        };
+        if table.rows.len() < self.min_rows.max(2) {
+            return;
+        }
+    
+        for column in columns(table) {
+            let cells = fields(table, column);
+            let (_, first) = cells[0];
+            if cells.len() < table.rows.len() || *first == Value::Null || cells.iter().any(|(_, cell)| cell != &first) {
+                continue;
+            }
+            let saved = TokenEstimator::estimate_fields(cells.iter().copied())
+                .saturating_sub(TokenEstimator::estimate_fields([(column, first)]));
+            ctx.report(
+                &cell_path(node.path, 0, column),
+                format!(
+                    "Column {} is {} in all {} rows of {}; stating it once outside the table would save ~{} tokens",
+                    column,
+                    first.to_json(),
+                    table.rows.len(),
+                    table.name,
+                    saved
+                ),
+            );
+        }
+    }
+}
+
+/// Notes table columns that are null or missing in at least `threshold`
+/// (default 0.95) of the rows. Tables with fewer than `min_rows` rows
+/// (default 3) are skipped.
+pub struct SparseColumns {
+    threshold: f64,
+    min_rows: usize,
+}
+
+impl Default for SparseColumns {
+    fn default() -> Self {
+        SparseColumns { threshold: 0.95, min_rows: 3 }
+    }
+}
+
+impl Rule for SparseColumns {
+    fn id(&self) -> &'static str {
+        "sparse-column"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::SPARSE_COLUMN
+    }
+    
+    fn description(&self) -> &'static str {
+        "Table columns should hold values in most rows"
+    }
+    
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Info
+    }
+    
+    fn configure(&mut self, options: &RuleOptions) -> Result<()> {
+        for (name, option) in options {
+            match name.as_str() {
+                "threshold" => {
+                    self.threshold = option.as_f64().filter(|t| (0.0..=1.0).contains(t)).ok_or_else(|| {
+                        TqError::InvalidConfig("sparse-column: 'threshold' must be a fraction between 0 and 1".to_string())
+                    })?;
+                }
+                "min_rows" => self.min_rows = min_rows_option(self.id(), option)?,
+                other => return Err(unknown_option(self.id(), other)),
+            }
+        }
+        Ok(())
+    }
+    
+    fn check(&self, node: &Node, ctx: &mut RuleContext) {
+        let Value::Table(table) = node.value else {
+            return;
+        };
+        let rows = table.rows.len();
+        if rows < self.min_rows.max(1) {
+            return;
+        }
+    
+        for column in columns(table) {
+            let cells = fields(table, column);
+            let filled = cells.iter().filter(|(_, cell)| **cell != Value::Null).count();
+            let empty = rows - filled;
+            // Missing cells cost nothing, so without nulls there's nothing to save
+            let Some(first_null) = table.rows.iter().position(|row| row.get(column) == Some(&Value::Null)) else {
+                continue;
+            };
+            if (empty as f64) < self.threshold * rows as f64 {
+                continue;
+            }
+    
+            let nulls = cells.iter().copied().filter(|(_, cell)| **cell == Value::Null);
+            let saved = TokenEstimator::estimate_fields(nulls);
+            let remedy = if filled == 0 { "dropping the column" } else { "leaving out the nulls" };
+            ctx.report(
+                &cell_path(node.path, first_null, column),
+                format!(
+                    "Column {} is empty in {} of {} rows of {}; {} would save ~{} tokens",
+                    column, empty, rows, table.name, remedy, saved
+                ),
+            );
+        }
+    }
+}
+
+/// Notes table rows that repeat an earlier row in every column.
+#[derive(Default)]
+pub struct DuplicateRows;
+
+impl Rule for DuplicateRows {
+    fn id(&self) -> &'static str {
+        "duplicate-row"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::DUPLICATE_ROW
+    }
+    
+    fn description(&self) -> &'static str {
+        "Table rows should not repeat earlier rows"
+    }
+    
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Info
+    }
+    
+    fn check(&self, node: &Node, ctx: &mut RuleContext) {
+        let Value::Table(table) = node.value else {
+            return;
+        };
+    
+        // Whole rows are compared, so a column missing from both still matches
+        let mut first_seen: HashMap<String, usize> = HashMap::new();
+        for (idx, row) in table.rows.iter().enumerate() {
+            let fingerprint = Value::Object(row.clone()).to_json().to_string();
+            let Some(&first) = first_seen.get(&fingerprint) else {
+                first_seen.insert(fingerprint, idx);
+                continue;
+            };
+            let saved = TokenEstimator::estimate_fields(row);
+            ctx.report(
+                &path::join_index(node.path, idx),
+                format!(
+                    "Row {} of {} repeats row {}; removing it would save ~{} tokens",
+                    idx + 1,
+                    table.name,
+                    first + 1,
+                    saved
+                ),
+            );
+        }
+    }
+}
+
+/// Notes table columns whose value always follows from another column's,
+/// such as a `country_name` next to a `country_code`. The other column's
+/// values must repeat (on average twice), since a unique column trivially
+/// determines every other one. Tables with fewer than `min_rows` rows
+/// (default 3) are skipped.
+pub struct DerivedColumns {
+    min_rows: usize,
+}
+
+impl Default for DerivedColumns {
+    fn default() -> Self {
+        DerivedColumns { min_rows: 3 }
+    }
+}
+
+/// Whether each source value always comes with the same target value.
+fn determines(source: &[String], target: &[String]) -> bool {
+    let mut seen: HashMap<&String, &String> = HashMap::new();
+    source.iter().zip(target).all(|(s, t)| *seen.entry(s).or_insert(t) == t)
+}
+
+fn distinct(values: &[String]) -> usize {
+    values.iter().collect::<HashSet<_>>().len()
+}
+
+impl Rule for DerivedColumns {
+    fn id(&self) -> &'static str {
+        "derived-column"
+    }
+    
+    fn code(&self) -> &'static str {
+        codes::DERIVED_COLUMN
+    }
+    
+    fn description(&self) -> &'static str {
+        "Table columns should not be derivable from other columns"
+    }
+    
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Info
+    }
+    
+    fn configure(&mut self, options: &RuleOptions) -> Result<()> {
+        for (name, option) in options {
+            match name.as_str() {
+                "min_rows" => self.min_rows = min_rows_option(self.id(), option)?,
+                other => return Err(unknown_option(self.id(), other)),
+            }
+        }
+        Ok(())
+    }
+    
+    fn check(&self, node: &Node, ctx: &mut RuleContext) {
+        let Value::Table(table) = node.value else {
+            return;
+        };
+        let rows = table.rows.len();
+        if rows < self.min_rows.max(2) {
+            return;
+        }
+    
+        // Values aren't hashable (floats), so compare their JSON form.
+        // Only columns present in every row are considered.
+        let values: Vec<(&String, Vec<String>)> = columns(table)
+            .into_iter()
+            .filter_map(|column| {
+                let cells: Option<Vec<String>> = table
+                    .rows
+                    .iter()
+                    .map(|row| row.get(column).map(|cell| cell.to_json().to_string()))
+                    .collect();
+                Some((column, cells?))
+            })
+            .collect();
+    
+        for (target, target_values) in &values {
+            // Constant columns are `constant-column`'s to report
+            if distinct(target_values) < 2 {
+                continue;
+            }
+            let source = values.iter().find(|(source, source_values)| {
+                source != target
+                    && distinct(source_values) * 2 <= rows
+                    && determines(source_values, target_values)
+                    // Of two columns that determine each other, report the later one
+                    && !(target < source && determines(target_values, source_values))
+            });
+            let Some((source, source_values)) = source else {
+                continue;
+            };
+    
+            // A lookup table keeps one target value per source value
+            let mut kept = HashSet::new();
+            let lookup: Vec<(&String, &Value)> = table
+                .rows
+                .iter()
+                .zip(source_values)
+                .filter(|(_, source_value)| kept.insert(*source_value))
+                .filter_map(|(row, _)| row.get_key_value(*target))
+                .collect();
+            let saved = TokenEstimator::estimate_fields(fields(table, target))
+                .saturating_sub(TokenEstimator::estimate_fields(lookup));
+            if saved == 0 {
+                continue;
+            }
+            ctx.report(
+                &cell_path(node.path, 0, target),
+                format!(
+                    "Column {} of {} is determined by {} ({} distinct values); a lookup table would save ~{} tokens",
+                    target,
+                    table.name,
+                    source,
+                    distinct(source_values),
+                    saved
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{diagnostic::Severity, rules::{RuleLevel, RuleRegistry}, toon_parser::ToonParser};
+
+    fn notes(input: &str) -> Vec<String> {
+        let mut registry = RuleRegistry::with_defaults();
+        registry.set_level("unique-keys", RuleLevel::Off).unwrap();
+        let diagnostics = registry.run(&ToonParser::parse(input).unwrap());
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Info));
+        diagnostics.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_constant_and_sparse_columns() {
+        let input = r#"events[4]:
+  - id: 1
+    source: "web"
+    note: null
+    extra: null
+  - id: 2
+    source: "web"
+    note: null
+    extra: null
+  - id: 3
+    source: "web"
+    note: "late"
+    extra: null
+  - id: 4
+    source: "web"
+    note: null
+    extra: null"#;
+
+        assert_eq!(notes(input), vec![
+            "[TV0602] events[0].source: Column source is \"web\" in all 4 rows of events; stating it once outside the table would save ~9 tokens",
+            "[TV0603] events[0].extra: Column extra is empty in 4 of 4 rows of events; dropping the column would save ~11 tokens",
+        ]);
+    }
+
+    #[test]
+    fn test_duplicate_rows_and_derived_columns() {
+        let input = r#"customers[5]:
+  - id: 1
+    country: "NL"
+    country_name: "Netherlands"
+  - id: 2
+    country: "NL"
+    country_name: "Netherlands"
+  - id: 3
+    country: "DE"
+    country_name: "Germany"
+  - id: 3
+    country: "DE"
+    country_name: "Germany"
+  - id: 4
+    country: "NL"
+    country_name: "Netherlands""#;
+
+        assert_eq!(notes(input), vec![
+            "[TV0604] customers[3]: Row 4 of customers repeats row 3; removing it would save ~11 tokens",
+            "[TV0605] customers[0].country_name: Column country_name of customers is determined by country (2 distinct values); a lookup table would save ~19 tokens",
+        ]);
+    }
+    
+    #[test]
+    fn test_duplicate_rows_missing_a_column() {
+        let input = r#"tags[3]:
+  - name: "a"
+    color: "red"
+  - name: "b"
+  - name: "b""#;
+    
+        assert_eq!(notes(input), vec![
+            "[TV0604] tags[2]: Row 3 of tags repeats row 2; removing it would save ~3 tokens",
+        ]);
+    }
+}
@@ -0,0 +1,670 @@
+use crate::{
+    error::{Result, TqError},
+    format::StringFormat,
+    json_parser::JsonParser,
+    path,
+    rules::{parse_foreign_keys, parse_key, parse_key_list, ForeignKey},
+    value::{find_duplicate_rows, Value},
+};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// A JSON Schema (draft 2020-12 subset) compiled for validating `Value`s.
+///
+/// Supported keywords: `type`, `required`, `properties`,
+/// `additionalProperties`, `items`, `enum`, `pattern`, `format`, `minimum`,
+/// `maximum`, `exclusiveMinimum`, `exclusiveMaximum`, `minLength`,
+/// `maxLength`, `minItems` and `maxItems`. Other keywords are ignored, as are
+/// formats other than `date`, `date-time`, `time`, `email`, `uri` and `uuid`.
+/// Tables are validated as arrays of objects.
+///
+/// Two extensions constrain the rows of tables and arrays of objects:
+/// `primaryKey` (a column or list of columns that must be present, non-null
+/// and unique) and `uniqueKeys` (a list of columns or column lists that must
+/// be unique). At the top level, `foreignKeys` maps `"table.column"` to the
+/// `"table.column"` it references, e.g. `{"orders.user_id": "users.id"}`.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    pub root: SchemaNode,
+    pub foreign_keys: Vec<ForeignKey>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SchemaType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum AdditionalProperties {
+    #[default]
+    Allowed,
+    Forbidden,
+    Schema(Box<SchemaNode>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SchemaNode {
+    /// The `false` schema, which no value satisfies
+    pub reject_all: bool,
+    pub types: Option<Vec<SchemaType>>,
+    pub required: Vec<String>,
+    pub properties: BTreeMap<String, SchemaNode>,
+    pub additional_properties: AdditionalProperties,
+    pub items: Option<Box<SchemaNode>>,
+    pub enum_values: Option<Vec<Value>>,
+    pub pattern: Option<Regex>,
+    pub format: Option<StringFormat>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub exclusive_minimum: Option<f64>,
+    pub exclusive_maximum: Option<f64>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>,
+    /// Columns whose values identify each row of a table or array of
+    /// objects; they must be present, non-null and unique (`primaryKey`)
+    pub primary_key: Option<Vec<String>>,
+    /// Column sets that must be unique across rows (`uniqueKeys`)
+    pub unique_keys: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaViolation {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl SchemaType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "null" => Some(SchemaType::Null),
+            "boolean" => Some(SchemaType::Boolean),
+            "integer" => Some(SchemaType::Integer),
+            "number" => Some(SchemaType::Number),
+            "string" => Some(SchemaType::String),
+            "array" => Some(SchemaType::Array),
+            "object" => Some(SchemaType::Object),
+            _ => None,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaType::Null => "null",
+            SchemaType::Boolean => "boolean",
+            SchemaType::Integer => "integer",
+            SchemaType::Number => "number",
+            SchemaType::String => "string",
+            SchemaType::Array => "array",
+            SchemaType::Object => "object",
+        }
+    }
+    
+    fn matches(&self, value: &Value) -> bool {
+        match (self, value) {
+            (SchemaType::Null, Value::Null) => true,
+            (SchemaType::Boolean, Value::Bool(_)) => true,
+            (SchemaType::Integer, Value::Number(n)) => n.fract() == 0.0,
+            (SchemaType::Number, Value::Number(_)) => true,
+            (SchemaType::String, Value::String(_)) => true,
+            (SchemaType::Array, Value::Array(_) | Value::Table(_)) => true,
+            (SchemaType::Object, Value::Object(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Schema {
+    pub fn parse(input: &str) -> Result<Self> {
+        let json: serde_json::Value = serde_json::from_str(input)?;
+        Self::from_json(&json)
+    }
+    
+    pub fn from_json(json: &serde_json::Value) -> Result<Self> {
+        let foreign_keys = match json.get("foreignKeys") {
+            Some(references) => parse_foreign_keys(references).ok_or_else(|| {
+                SchemaNode::invalid("#", "'foreignKeys' must map \"table.column\" to \"table.column\"")
+            })?,
+            None => Vec::new(),
+        };
+        Ok(Schema {
+            root: SchemaNode::from_json(json, "#")?,
+            foreign_keys,
+        })
+    }
+    
+    /// Returns every violation found, in document order of the schema.
+    pub fn validate(&self, value: &Value) -> Vec<SchemaViolation> {
+        let mut violations = Vec::new();
+        self.root.validate(value, "", &mut violations);
+        for foreign_key in &self.foreign_keys {
+            for table in foreign_key.missing_tables(value) {
+                violations.push(SchemaViolation {
+                    path: String::new(),
+                    message: format!("unknown table {} in foreign key {}", table, foreign_key),
+                });
+            }
+            for reference in foreign_key.dangling(value) {
+                violations.push(SchemaViolation {
+                    path: reference.path.clone(),
+                    message: format!("dangling reference: {}", reference.message(foreign_key)),
+                });
+            }
+        }
+        violations
+    }
+}
+
+impl SchemaNode {
+    fn from_json(json: &serde_json::Value, location: &str) -> Result<Self> {
+        let obj = match json {
+            serde_json::Value::Bool(allow) => {
+                return Ok(SchemaNode {
+                    reject_all: !allow,
+                    ..Default::default()
+                });
+            }
+            serde_json::Value::Object(obj) => obj,
+            _ => return Err(Self::invalid(location, "schema must be an object or boolean")),
+        };
+        
+        let mut node = SchemaNode::default();
+        
+        if let Some(types) = obj.get("type") {
+            let names: Vec<&serde_json::Value> = match types {
+                serde_json::Value::Array(arr) => arr.iter().collect(),
+                other => vec![other],
+            };
+            let mut parsed = Vec::new();
+            for name in names {
+                let schema_type = name
+                    .as_str()
+                    .and_then(SchemaType::from_name)
+                    .ok_or_else(|| Self::invalid(location, &format!("unknown type {}", name)))?;
+                parsed.push(schema_type);
+            }
+            node.types = Some(parsed);
+        }
+        
+        if let Some(required) = obj.get("required") {
+            let arr = required
+                .as_array()
+                .ok_or_else(|| Self::invalid(location, "'required' must be an array"))?;
+            for key in arr {
+                let key = key
+                    .as_str()
+                    .ok_or_else(|| Self::invalid(location, "'required' entries must be strings"))?;
+                node.required.push(key.to_string());
+            }
+        }
+        
+        if let Some(properties) = obj.get("properties") {
+            let props = properties
+                .as_object()
+                .ok_or_else(|| Self::invalid(location, "'properties' must be an object"))?;
+            for (key, sub) in props {
+                let sub_location = format!("{}/properties/{}", location, key);
+                node.properties.insert(key.clone(), SchemaNode::from_json(sub, &sub_location)?);
+            }
+        }
+        
+        node.additional_properties = match obj.get("additionalProperties") {
+            None | Some(serde_json::Value::Bool(true)) => AdditionalProperties::Allowed,
+            Some(serde_json::Value::Bool(false)) => AdditionalProperties::Forbidden,
+            Some(sub) => {
+                let sub_location = format!("{}/additionalProperties", location);
+                AdditionalProperties::Schema(Box::new(SchemaNode::from_json(sub, &sub_location)?))
+            }
+        };
+        
+        if let Some(items) = obj.get("items") {
+            let sub_location = format!("{}/items", location);
+            node.items = Some(Box::new(SchemaNode::from_json(items, &sub_location)?));
+        }
+        
+        if let Some(values) = obj.get("enum") {
+            let arr = values
+                .as_array()
+                .ok_or_else(|| Self::invalid(location, "'enum' must be an array"))?;
+            node.enum_values = Some(arr.iter().cloned().map(JsonParser::convert_json_to_value).collect());
+        }
+        
+        if let Some(pattern) = obj.get("pattern") {
+            let source = pattern
+                .as_str()
+                .ok_or_else(|| Self::invalid(location, "'pattern' must be a string"))?;
+            let regex = Regex::new(source)
+                .map_err(|e| Self::invalid(location, &format!("invalid pattern: {}", e)))?;
+            node.pattern = Some(regex);
+        }
+        
+        if let Some(format) = obj.get("format") {
+            let name = format
+                .as_str()
+                .ok_or_else(|| Self::invalid(location, "'format' must be a string"))?;
+            node.format = name.parse().ok();
+        }
+        
+        node.minimum = Self::number_keyword(obj, "minimum", location)?;
+        node.maximum = Self::number_keyword(obj, "maximum", location)?;
+        node.exclusive_minimum = Self::number_keyword(obj, "exclusiveMinimum", location)?;
+        node.exclusive_maximum = Self::number_keyword(obj, "exclusiveMaximum", location)?;
+        node.min_length = Self::count_keyword(obj, "minLength", location)?;
+        node.max_length = Self::count_keyword(obj, "maxLength", location)?;
+        node.min_items = Self::count_keyword(obj, "minItems", location)?;
+        node.max_items = Self::count_keyword(obj, "maxItems", location)?;
+        
+        if let Some(key) = obj.get("primaryKey") {
+            node.primary_key = Some(parse_key(key).ok_or_else(|| {
+                Self::invalid(location, "'primaryKey' must be a column name or a list of column names")
+            })?);
+        }
+        
+        if let Some(keys) = obj.get("uniqueKeys") {
+            node.unique_keys = parse_key_list(keys).ok_or_else(|| {
+                Self::invalid(location, "'uniqueKeys' must be a list of column names or column lists")
+            })?;
+        }
+        
+        Ok(node)
+    }
+    
+    fn number_keyword(
+        obj: &serde_json::Map<String, serde_json::Value>,
+        keyword: &str,
+        location: &str,
+    ) -> Result<Option<f64>> {
+        match obj.get(keyword) {
+            None => Ok(None),
+            Some(v) => v
+                .as_f64()
+                .map(Some)
+                .ok_or_else(|| Self::invalid(location, &format!("'{}' must be a number", keyword))),
+        }
+    }
+    
+    fn count_keyword(
+        obj: &serde_json::Map<String, serde_json::Value>,
+        keyword: &str,
+        location: &str,
+    ) -> Result<Option<usize>> {
+        match obj.get(keyword) {
+            None => Ok(None),
+            Some(v) => v
+                .as_u64()
+                .map(|n| Some(n as usize))
+                .ok_or_else(|| Self::invalid(location, &format!("'{}' must be a non-negative integer", keyword))),
+        }
+    }
+    
+    fn invalid(location: &str, message: &str) -> TqError {
+        TqError::InvalidSchema(format!("{}: {}", location, message))
+    }
+    
+    fn validate(&self, value: &Value, path: &str, violations: &mut Vec<SchemaViolation>) {
+        let mut report = |message: String| {
+            violations.push(SchemaViolation {
+                path: path.to_string(),
+                message,
+            });
+        };
+        
+        if self.reject_all {
+            report("value is not allowed here".to_string());
+            return;
+        }
+        
+        if let Some(types) = &self.types {
+            if !types.iter().any(|t| t.matches(value)) {
+                let expected: Vec<&str> = types.iter().map(SchemaType::name).collect();
+                report(format!(
+                    "expected {}, found {}",
+                    expected.join(" or "),
+                    value.type_name()
+                ));
+                // Remaining keywords would only repeat the type mismatch
+                return;
+            }
+        }
+        
+        if let Some(allowed) = &self.enum_values {
+            if !allowed.contains(value) {
+                let options: Vec<String> = allowed.iter().map(|v| v.to_json().to_string()).collect();
+                report(format!(
+                    "value {} is not one of [{}]",
+                    value.to_json(),
+                    options.join(", ")
+                ));
+            }
+        }
+        
+        match value {
+            Value::Number(n) => {
+                if let Some(min) = self.minimum.filter(|min| n < min) {
+                    report(format!("{} is less than the minimum of {}", n, min));
+                }
+                if let Some(max) = self.maximum.filter(|max| n > max) {
+                    report(format!("{} is greater than the maximum of {}", n, max));
+                }
+                if let Some(min) = self.exclusive_minimum.filter(|min| n <= min) {
+                    report(format!("{} must be greater than {}", n, min));
+                }
+                if let Some(max) = self.exclusive_maximum.filter(|max| n >= max) {
+                    report(format!("{} must be less than {}", n, max));
+                }
+            }
+            Value::String(s) => {
+                let length = s.chars().count();
+                if let Some(min) = self.min_length.filter(|min| length < *min) {
+                    report(format!("string is shorter than {} characters", min));
+                }
+                if let Some(max) = self.max_length.filter(|max| length > *max) {
+                    report(format!("string is longer than {} characters", max));
+                }
+                if let Some(pattern) = self.pattern.as_ref().filter(|p| !p.is_match(s)) {
+                    report(format!("\"{}\" does not match pattern '{}'", s, pattern.as_str()));
+                }
+                if let Some(format) = self.format.filter(|f| !f.matches(s)) {
+                    report(format!("\"{}\" is not a valid {}", s, format));
+                }
+            }
+            Value::Array(items) => {
+                self.validate_items(items.len(), path, violations);
+                self.validate_keys(value, path, violations);
+                if let Some(item_schema) = &self.items {
+                    for (idx, item) in items.iter().enumerate() {
+                        item_schema.validate(item, &path::join_index(path, idx), violations);
+                    }
+                }
+            }
+            Value::Table(table) => {
+                self.validate_items(table.rows.len(), path, violations);
+                self.validate_keys(value, path, violations);
+                if let Some(item_schema) = &self.items {
+                    for (idx, row) in table.rows.iter().enumerate() {
+                        item_schema.validate_row(row, &path::join_index(path, idx), violations);
+                    }
+                }
+            }
+            Value::Object(obj) => self.validate_properties(obj, path, violations),
+            Value::Null | Value::Bool(_) => {}
+        }
+    }
+    
+    /// Table rows are validated as if they were objects.
+    fn validate_row(&self, row: &HashMap<String, Value>, path: &str, violations: &mut Vec<SchemaViolation>) {
+        if self.reject_all {
+            violations.push(SchemaViolation {
+                path: path.to_string(),
+                message: "value is not allowed here".to_string(),
+            });
+            return;
+        }
+        
+        if let Some(types) = self.types.as_ref().filter(|t| !t.contains(&SchemaType::Object)) {
+            let expected: Vec<&str> = types.iter().map(SchemaType::name).collect();
+            violations.push(SchemaViolation {
+                path: path.to_string(),
+                message: format!("expected {}, found table row", expected.join(" or ")),
+            });
+            return;
+        }
+        
+        self.validate_properties(row, path, violations);
+    }
+    
+    fn validate_items(&self, count: usize, path: &str, violations: &mut Vec<SchemaViolation>) {
+        if let Some(min) = self.min_items.filter(|min| count < *min) {
+            violations.push(SchemaViolation {
+                path: path.to_string(),
+                message: format!("expected at least {} items, found {}", min, count),
+            });
+        }
+        if let Some(max) = self.max_items.filter(|max| count > *max) {
+            violations.push(SchemaViolation {
+                path: path.to_string(),
+                message: format!("expected at most {} items, found {}", max, count),
+            });
+        }
+    }
+    
+    fn validate_keys(&self, value: &Value, path: &str, violations: &mut Vec<SchemaViolation>) {
+        let Some(rows) = value.object_rows() else {
+            return;
+        };
+        
+        if let Some(primary_key) = &self.primary_key {
+            for (idx, row) in rows.iter().enumerate() {
+                let Some(row) = row else {
+                    continue;
+                };
+                for column in primary_key {
+                    let problem = match row.get(column) {
+                        None => "missing",
+                        Some(Value::Null) => "null",
+                        Some(_) => continue,
+                    };
+                    violations.push(SchemaViolation {
+                        path: path::join_index(path, idx),
+                        message: format!("primary key column '{}' is {}", column, problem),
+                    });
+                }
+            }
+        }
+        
+        for key in self.primary_key.iter().chain(&self.unique_keys) {
+            for duplicate in find_duplicate_rows(rows.iter().copied(), key) {
+                violations.push(SchemaViolation {
+                    path: path::join_index(path, duplicate.duplicate),
+                    message: duplicate.message(key),
+                });
+            }
+        }
+    }
+    
+    fn validate_properties(&self, obj: &HashMap<String, Value>, path: &str, violations: &mut Vec<SchemaViolation>) {
+        for key in &self.required {
+            if !obj.contains_key(key) {
+                violations.push(SchemaViolation {
+                    path: path.to_string(),
+                    message: format!("missing required property '{}'", key),
+                });
+            }
+        }
+        
+        // Sort keys so violations come out in a stable order
+        let mut keys: Vec<&String> = obj.keys().collect();
+        keys.sort();
+        
+        for key in keys {
+            let child_path = path::join_key(path, key);
+            let child = &obj[key];
+            
+            match (self.properties.get(key), &self.additional_properties) {
+                (Some(sub), _) => sub.validate(child, &child_path, violations),
+                (None, AdditionalProperties::Allowed) => {}
+                (None, AdditionalProperties::Forbidden) => violations.push(SchemaViolation {
+                    path: path.to_string(),
+                    message: format!("additional property '{}' is not allowed", key),
+                }),
+                (None, AdditionalProperties::Schema(sub)) => sub.validate(child, &child_path, violations),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toon_parser::ToonParser;
+
+    fn schema(json: &str) -> Schema {
+        Schema::parse(json).unwrap()
+    }
+
+    #[test]
+    fn test_type_and_required() {
+        let schema = schema(r#"{
+            "type": "object",
+            "required": ["name", "age"],
+            "properties": {
+                "name": {"type": "string"},
+                "age": {"type": "integer", "minimum": 0}
+            }
+        }"#);
+        
+        let value = ToonParser::parse("name: 42\nextra: true").unwrap();
+        let violations = schema.validate(&value);
+        
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].path, "");
+        assert!(violations[0].message.contains("missing required property 'age'"));
+        assert_eq!(violations[1].path, "name");
+        assert!(violations[1].message.contains("expected string, found number"));
+    }
+
+    #[test]
+    fn test_table_validated_as_array_of_objects() {
+        let schema = schema(r#"{
+            "properties": {
+                "users": {
+                    "type": "array",
+                    "maxItems": 1,
+                    "items": {
+                        "type": "object",
+                        "required": ["id"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "id": {"type": "integer"},
+                            "role": {"enum": ["admin", "user"]}
+                        }
+                    }
+                }
+            }
+        }"#);
+        
+        let input = r#"users[2]:
+  - id: 1
+    role: "admin"
+  - id: 1.5
+    role: "owner"
+    email: "x@example.com""#;
+        let value = ToonParser::parse(input).unwrap();
+        let violations = schema.validate(&value);
+        
+        let rendered: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+        assert_eq!(rendered, vec![
+            "users: expected at most 1 items, found 2",
+            "users[1]: additional property 'email' is not allowed",
+            "users[1].id: expected integer, found number",
+            "users[1].role: value \"owner\" is not one of [\"admin\", \"user\"]",
+        ]);
+    }
+
+    #[test]
+    fn test_string_constraints() {
+        let schema = schema(r#"{"type": "string", "pattern": "^[A-Z]{3}-\\d+$", "maxLength": 6}"#);
+        
+        assert!(schema.validate(&Value::String("ABC-12".to_string())).is_empty());
+        
+        let violations = schema.validate(&Value::String("abc-1234".to_string()));
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].message.contains("longer than 6"));
+        assert!(violations[1].message.contains("does not match pattern"));
+    }
+
+    #[test]
+    fn test_string_formats() {
+        let schema = schema(r#"{"properties": {"created_at": {"format": "date-time"}, "id": {"format": "ipv4"}}}"#);
+        
+        let value = ToonParser::parse("created_at: \"2024-01-05T10:00:00Z\"\nid: x").unwrap();
+        assert!(schema.validate(&value).is_empty());
+        
+        let violations = schema.validate(&ToonParser::parse("created_at: yesterday\nid: x").unwrap());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].to_string(), "created_at: \"yesterday\" is not a valid date-time");
+        assert!(matches!(Schema::parse(r#"{"format": 1}"#), Err(TqError::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn test_primary_and_unique_keys() {
+        let schema = schema(r#"{
+            "properties": {
+                "users": {"items": {"type": "object"}, "primaryKey": "id", "uniqueKeys": [["first", "last"]]}
+            }
+        }"#);
+        let input = r#"users[4]:
+  - id: 1
+    first: "Ada"
+    last: "L"
+  - id: 2
+    first: "Ada"
+    last: "L"
+  - id: 1
+    first: "Bob"
+    last: "K"
+  - id: null
+    first: "Cy"
+    last: "M""#;
+        let rendered: Vec<String> = schema
+            .validate(&ToonParser::parse(input).unwrap())
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        
+        assert_eq!(rendered, vec![
+            "users[3]: primary key column 'id' is null",
+            "users[2]: duplicate id 1 in rows 1 and 3",
+            "users[1]: duplicate (first, last) (\"Ada\", \"L\") in rows 1 and 2",
+        ]);
+        assert!(matches!(Schema::parse(r#"{"primaryKey": 3}"#), Err(TqError::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn test_foreign_keys() {
+        let schema = schema(r#"{"foreignKeys": {"orders.user_id": "users.id"}}"#);
+        let input = r#"users[1]:
+  - id: 1
+orders[2]:
+  - user_id: 1
+  - user_id: 7"#;
+        let rendered: Vec<String> = schema
+            .validate(&ToonParser::parse(input).unwrap())
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        
+        assert_eq!(rendered, vec!["orders[1].user_id: dangling reference: user_id 7 in orders row 2 matches no users.id"]);
+        
+        let misspelt = Schema::parse(r#"{"foreignKeys": {"orders.user_id": "user.id"}}"#).unwrap();
+        let violations = misspelt.validate(&ToonParser::parse(input).unwrap());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].message, "unknown table user in foreign key orders.user_id -> user.id");
+        assert!(matches!(Schema::parse(r#"{"foreignKeys": ["orders.user_id"]}"#), Err(TqError::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        assert!(matches!(Schema::parse(r#"{"type": "text"}"#), Err(TqError::InvalidSchema(_))));
+        assert!(matches!(Schema::parse(r#"{"pattern": "("}"#), Err(TqError::InvalidSchema(_))));
+    }
+}
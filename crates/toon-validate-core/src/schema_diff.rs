@@ -0,0 +1,323 @@
+//! Compares two schemas and classifies each difference as breaking or not.
+//!
+//! A change is breaking when documents that satisfied the old schema can fail
+//! the new one (a narrowed type, a new required field, a tighter bound), or
+//! when consumers lose something the old schema guaranteed (a required field
+//! that was removed or made optional).
+
+use crate::{
+    path,
+    schema::{AdditionalProperties, Schema, SchemaNode, SchemaType},
+};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    Breaking,
+    NonBreaking,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaChange {
+    pub kind: ChangeKind,
+    /// Value path the change applies to, with `[]` for array items, e.g.
+    /// `users[].email`; empty for the root
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Lists the changes from `old` to `new`, breaking changes first.
+pub fn diff_schemas(old: &Schema, new: &Schema) -> Vec<SchemaChange> {
+    let mut changes = Changes::default();
+    changes.node(&old.root, &new.root, "");
+    
+    let old_references: BTreeSet<String> = old.foreign_keys.iter().map(|fk| fk.to_string()).collect();
+    let new_references: BTreeSet<String> = new.foreign_keys.iter().map(|fk| fk.to_string()).collect();
+    for added in new_references.difference(&old_references) {
+        changes.breaking("", format!("foreign key {} added", added));
+    }
+    for removed in old_references.difference(&new_references) {
+        changes.non_breaking("", format!("foreign key {} removed", removed));
+    }
+    
+    // Stable sort keeps schema order within each kind
+    changes.0.sort_by_key(|change| change.kind);
+    changes.0
+}
+
+#[derive(Default)]
+struct Changes(Vec<SchemaChange>);
+
+impl Changes {
+    fn breaking(&mut self, path: &str, message: String) {
+        self.push(ChangeKind::Breaking, path, message);
+    }
+    
+    fn non_breaking(&mut self, path: &str, message: String) {
+        self.push(ChangeKind::NonBreaking, path, message);
+    }
+    
+    fn push(&mut self, kind: ChangeKind, path: &str, message: String) {
+        self.0.push(SchemaChange { kind, path: path.to_string(), message });
+    }
+    
+    /// Records a change that is breaking when `tighter`.
+    fn either(&mut self, tighter: bool, path: &str, message: String) {
+        let kind = if tighter { ChangeKind::Breaking } else { ChangeKind::NonBreaking };
+        self.push(kind, path, message);
+    }
+    
+    fn node(&mut self, old: &SchemaNode, new: &SchemaNode, at: &str) {
+        if old.reject_all != new.reject_all {
+            let message = if new.reject_all { "no longer allowed" } else { "now allowed" };
+            self.either(new.reject_all, at, message.to_string());
+            return;
+        }
+    
+        self.types(old.types.as_deref(), new.types.as_deref(), at);
+        self.enums(old, new, at);
+        self.properties(old, new, at);
+        self.bounds(old, new, at);
+    
+        match (&old.items, &new.items) {
+            (Some(old_items), Some(new_items)) => self.node(old_items, new_items, &format!("{}[]", at)),
+            (None, Some(_)) => self.breaking(at, "item schema added".to_string()),
+            (Some(_), None) => self.non_breaking(at, "item schema removed".to_string()),
+            (None, None) => {}
+        }
+    }
+    
+    fn types(&mut self, old: Option<&[SchemaType]>, new: Option<&[SchemaType]>, at: &str) {
+        let describe = |types: Option<&[SchemaType]>| match types {
+            Some(types) => types.iter().map(SchemaType::name).collect::<Vec<_>>().join(" or "),
+            None => "any".to_string(),
+        };
+        let covers = |wide: Option<&[SchemaType]>, narrow: Option<&[SchemaType]>| match (wide, narrow) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(wide), Some(narrow)) => narrow.iter().all(|t| {
+                wide.contains(t) || (*t == SchemaType::Integer && wide.contains(&SchemaType::Number))
+            }),
+        };
+    
+        match (covers(new, old), covers(old, new)) {
+            (true, true) => {}
+            (true, false) => self.non_breaking(at, format!("type widened from {} to {}", describe(old), describe(new))),
+            (false, true) => self.breaking(at, format!("type narrowed from {} to {}", describe(old), describe(new))),
+            (false, false) => self.breaking(at, format!("type changed from {} to {}", describe(old), describe(new))),
+        }
+    }
+    
+    fn enums(&mut self, old: &SchemaNode, new: &SchemaNode, at: &str) {
+        let render = |values: &[crate::value::Value]| {
+            values.iter().map(|v| v.to_json().to_string()).collect::<Vec<_>>().join(", ")
+        };
+        match (&old.enum_values, &new.enum_values) {
+            (None, Some(values)) => self.breaking(at, format!("values restricted to [{}]", render(values))),
+            (Some(_), None) => self.non_breaking(at, "value restriction removed".to_string()),
+            (Some(old_values), Some(new_values)) => {
+                let removed: Vec<_> = old_values.iter().filter(|v| !new_values.contains(v)).cloned().collect();
+                let added: Vec<_> = new_values.iter().filter(|v| !old_values.contains(v)).cloned().collect();
+                if !removed.is_empty() {
+                    self.breaking(at, format!("allowed values removed: {}", render(&removed)));
+                }
+                if !added.is_empty() {
+                    self.non_breaking(at, format!("allowed values added: {}", render(&added)));
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    
+    fn properties(&mut self, old: &SchemaNode, new: &SchemaNode, at: &str) {
+        let keys: BTreeSet<&String> = old
+            .properties
+            .keys()
+            .chain(new.properties.keys())
+            .chain(old.required.iter())
+            .chain(new.required.iter())
+            .collect();
+    
+        for key in keys {
+            let field = path::join_key(at, key);
+            let was_required = old.required.contains(key);
+            let is_required = new.required.contains(key);
+            let old_schema = old.properties.get(key);
+            let new_schema = new.properties.get(key);
+    
+            match (old_schema, new_schema) {
+                (Some(_), None) if was_required => self.breaking(&field, "required field removed".to_string()),
+                (Some(_), None) => {
+                    let forbidden = matches!(new.additional_properties, AdditionalProperties::Forbidden);
+                    self.either(forbidden, &field, "optional field removed".to_string());
+                }
+                (None, Some(_)) if is_required => self.breaking(&field, "new required field".to_string()),
+                (None, Some(_)) => self.non_breaking(&field, "new optional field".to_string()),
+                _ => {
+                    if was_required && !is_required {
+                        self.breaking(&field, "no longer required".to_string());
+                    } else if !was_required && is_required {
+                        self.breaking(&field, "now required".to_string());
+                    }
+                    if let (Some(old_schema), Some(new_schema)) = (old_schema, new_schema) {
+                        self.node(old_schema, new_schema, &field);
+                    }
+                }
+            }
+        }
+    
+        match (&old.additional_properties, &new.additional_properties) {
+            (AdditionalProperties::Forbidden, AdditionalProperties::Forbidden)
+            | (AdditionalProperties::Allowed, AdditionalProperties::Allowed) => {}
+            (_, AdditionalProperties::Forbidden) => self.breaking(at, "additional properties forbidden".to_string()),
+            (AdditionalProperties::Forbidden, _) => self.non_breaking(at, "additional properties allowed".to_string()),
+            (AdditionalProperties::Schema(old_extra), AdditionalProperties::Schema(new_extra)) => {
+                self.node(old_extra, new_extra, &path::join_key(at, "*"));
+            }
+            (AdditionalProperties::Allowed, AdditionalProperties::Schema(_)) => {
+                self.breaking(at, "additional properties restricted".to_string());
+            }
+            (AdditionalProperties::Schema(_), AdditionalProperties::Allowed) => {
+                self.non_breaking(at, "additional properties no longer restricted".to_string());
+            }
+        }
+    }
+    
+    fn bounds(&mut self, old: &SchemaNode, new: &SchemaNode, at: &str) {
+        let lower = [
+            ("minimum", old.minimum, new.minimum),
+            ("exclusiveMinimum", old.exclusive_minimum, new.exclusive_minimum),
+            ("minLength", old.min_length.map(|n| n as f64), new.min_length.map(|n| n as f64)),
+            ("minItems", old.min_items.map(|n| n as f64), new.min_items.map(|n| n as f64)),
+        ];
+        for (keyword, old_bound, new_bound) in lower {
+            self.bound(keyword, old_bound, new_bound, |old, new| new > old, at);
+        }
+        let upper = [
+            ("maximum", old.maximum, new.maximum),
+            ("exclusiveMaximum", old.exclusive_maximum, new.exclusive_maximum),
+            ("maxLength", old.max_length.map(|n| n as f64), new.max_length.map(|n| n as f64)),
+            ("maxItems", old.max_items.map(|n| n as f64), new.max_items.map(|n| n as f64)),
+        ];
+        for (keyword, old_bound, new_bound) in upper {
+            self.bound(keyword, old_bound, new_bound, |old, new| new < old, at);
+        }
+    
+        let old_pattern = old.pattern.as_ref().map(|p| p.as_str());
+        let new_pattern = new.pattern.as_ref().map(|p| p.as_str());
+        match (old_pattern, new_pattern) {
+            (None, Some(pattern)) => self.breaking(at, format!("pattern '{}' added", pattern)),
+            (Some(pattern), None) => self.non_breaking(at, format!("pattern '{}' removed", pattern)),
+            // Whether one pattern accepts all of another's strings isn't decidable here
+            (Some(old), Some(new)) if old != new => {
+                self.breaking(at, format!("pattern changed from '{}' to '{}'", old, new));
+            }
+            _ => {}
+        }
+    
+        match (old.format, new.format) {
+            (None, Some(format)) => self.breaking(at, format!("format {} added", format)),
+            (Some(format), None) => self.non_breaking(at, format!("format {} removed", format)),
+            (Some(old), Some(new)) if old != new => {
+                self.breaking(at, format!("format changed from {} to {}", old, new));
+            }
+            _ => {}
+        }
+        
+        let keys = |node: &SchemaNode| -> BTreeSet<Vec<String>> {
+            node.primary_key.iter().chain(&node.unique_keys).cloned().collect()
+        };
+        let (old_keys, new_keys) = (keys(old), keys(new));
+        for added in new_keys.difference(&old_keys) {
+            self.breaking(at, format!("unique key ({}) added", added.join(", ")));
+        }
+        for removed in old_keys.difference(&new_keys) {
+            self.non_breaking(at, format!("unique key ({}) removed", removed.join(", ")));
+        }
+    }
+    
+    fn bound(
+        &mut self,
+        keyword: &str,
+        old: Option<f64>,
+        new: Option<f64>,
+        tightened: impl Fn(f64, f64) -> bool,
+        at: &str,
+    ) {
+        match (old, new) {
+            (None, Some(new)) => self.breaking(at, format!("{} {} added", keyword, new)),
+            (Some(old), None) => self.non_breaking(at, format!("{} {} removed", keyword, old)),
+            (Some(old), Some(new)) if old != new => {
+                self.either(tightened(old, new), at, format!("{} changed from {} to {}", keyword, old, new));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(old: &str, new: &str) -> Vec<(ChangeKind, String)> {
+        diff_schemas(&Schema::parse(old).unwrap(), &Schema::parse(new).unwrap())
+            .into_iter()
+            .map(|change| (change.kind, change.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_classifies_field_changes() {
+        let old = r#"{
+            "properties": {
+                "users": {"type": "array", "items": {
+                    "properties": {"id": {"type": "integer"}, "email": {"type": "string"}, "age": {"type": ["integer", "null"]}},
+                    "required": ["id", "email"]
+                }}
+            }
+        }"#;
+        let new = r#"{
+            "properties": {
+                "users": {"type": "array", "items": {
+                    "properties": {"id": {"type": "number"}, "age": {"type": "integer"}, "nick": {"type": "string"}},
+                    "required": ["id"]
+                }}
+            }
+        }"#;
+    
+        assert_eq!(diff(old, new), vec![
+            (ChangeKind::Breaking, "users[].age: type narrowed from integer or null to integer".to_string()),
+            (ChangeKind::Breaking, "users[].email: required field removed".to_string()),
+            (ChangeKind::NonBreaking, "users[].id: type widened from integer to number".to_string()),
+            (ChangeKind::NonBreaking, "users[].nick: new optional field".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_constraints_and_enums() {
+        let old = r#"{"properties": {"role": {"enum": ["admin", "user"]}, "name": {"maxLength": 10}}}"#;
+        let new = r#"{"properties": {"role": {"enum": ["user", "guest"]}, "name": {"maxLength": 20, "pattern": "^[a-z]+$"}}, "additionalProperties": false}"#;
+    
+        assert_eq!(diff(old, new), vec![
+            (ChangeKind::Breaking, "name: pattern '^[a-z]+$' added".to_string()),
+            (ChangeKind::Breaking, "role: allowed values removed: \"admin\"".to_string()),
+            (ChangeKind::Breaking, "additional properties forbidden".to_string()),
+            (ChangeKind::NonBreaking, "name: maxLength changed from 10 to 20".to_string()),
+            (ChangeKind::NonBreaking, "role: allowed values added: \"guest\"".to_string()),
+        ]);
+        assert!(diff(old, old).is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+//! Infers a JSON Schema from sample documents by merging their shapes.
+
+use crate::{format::StringFormat, value::Value};
+use serde_json::{json, Map};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InferenceOptions {
+    /// Strings with at most this many distinct values become an `enum`
+    pub max_enum_values: usize,
+    /// Each enum value must have been seen this many times on average, so a
+    /// handful of one-off strings doesn't turn into an enum
+    pub min_enum_repeats: usize,
+}
+
+impl Default for InferenceOptions {
+    fn default() -> Self {
+        InferenceOptions {
+            max_enum_values: 8,
+            min_enum_repeats: 2,
+        }
+    }
+}
+
+/// What has been observed at one position in the documents.
+#[derive(Debug, Default)]
+struct Shape {
+    nulls: usize,
+    booleans: usize,
+    integers: usize,
+    /// Numbers with a fractional part
+    fractions: usize,
+    strings: usize,
+    /// Distinct string values, dropped once there are too many for an enum
+    string_values: Option<BTreeSet<String>>,
+    /// The format every string so far has had
+    string_format: Option<StringFormat>,
+    arrays: usize,
+    items: Option<Box<Shape>>,
+    objects: usize,
+    properties: BTreeMap<String, Shape>,
+    /// How many of the objects had each property
+    property_counts: BTreeMap<String, usize>,
+}
+
+pub struct SchemaInferrer {
+    options: InferenceOptions,
+    root: Shape,
+    samples: usize,
+}
+
+impl SchemaInferrer {
+    pub fn new(options: InferenceOptions) -> Self {
+        SchemaInferrer {
+            options,
+            root: Shape::default(),
+            samples: 0,
+        }
+    }
+    
+    /// Merges another sample document into the inferred shape.
+    pub fn add(&mut self, value: &Value) {
+        self.root.observe(value, &self.options);
+        self.samples += 1;
+    }
+    
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+    
+    /// The inferred JSON Schema, usable with `Schema::from_json`.
+    pub fn to_json_schema(&self) -> serde_json::Value {
+        let mut schema = self.root.to_json(&self.options);
+        if let serde_json::Value::Object(obj) = &mut schema {
+            obj.insert("$schema".to_string(), json!(JSON_SCHEMA_DIALECT));
+        }
+        schema
+    }
+}
+
+impl Default for SchemaInferrer {
+    fn default() -> Self {
+        Self::new(InferenceOptions::default())
+    }
+}
+
+impl Shape {
+    fn observe(&mut self, value: &Value, options: &InferenceOptions) {
+        match value {
+            Value::Null => self.nulls += 1,
+            Value::Bool(_) => self.booleans += 1,
+            Value::Number(n) if n.fract() == 0.0 => self.integers += 1,
+            Value::Number(_) => self.fractions += 1,
+            Value::String(s) => {
+                if self.strings == 0 {
+                    self.string_values = Some(BTreeSet::new());
+                    self.string_format = StringFormat::detect(s);
+                } else if self.string_format.is_some_and(|format| !format.matches(s)) {
+                    self.string_format = None;
+                }
+                self.strings += 1;
+                if let Some(values) = &mut self.string_values {
+                    values.insert(s.clone());
+                    if values.len() > options.max_enum_values {
+                        self.string_values = None;
+                    }
+                }
+            }
+            Value::Array(arr) => {
+                self.arrays += 1;
+                let items = self.items.get_or_insert_with(Default::default);
+                for item in arr {
+                    items.observe(item, options);
+                }
+            }
+            Value::Object(obj) => self.observe_object(obj, options),
+            // Tables are validated as arrays of row objects
+            Value::Table(table) => {
+                self.arrays += 1;
+                let items = self.items.get_or_insert_with(Default::default);
+                for row in &table.rows {
+                    items.observe_object(row, options);
+                }
+            }
+        }
+    }
+    
+    fn observe_object(&mut self, obj: &HashMap<String, Value>, options: &InferenceOptions) {
+        self.objects += 1;
+        for (key, value) in obj {
+            self.properties.entry(key.clone()).or_default().observe(value, options);
+            *self.property_counts.entry(key.clone()).or_default() += 1;
+        }
+    }
+    
+    fn type_names(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        if self.nulls > 0 {
+            names.push("null");
+        }
+        if self.booleans > 0 {
+            names.push("boolean");
+        }
+        if self.fractions > 0 {
+            names.push("number");
+        } else if self.integers > 0 {
+            names.push("integer");
+        }
+        if self.strings > 0 {
+            names.push("string");
+        }
+        if self.arrays > 0 {
+            names.push("array");
+        }
+        if self.objects > 0 {
+            names.push("object");
+        }
+        names
+    }
+    
+    fn to_json(&self, options: &InferenceOptions) -> serde_json::Value {
+        let mut schema = Map::new();
+    
+        let types = self.type_names();
+        match types.as_slice() {
+            // Never observed, e.g. the items of arrays that were always empty
+            [] => return json!({}),
+            [single] => schema.insert("type".to_string(), json!(single)),
+            _ => schema.insert("type".to_string(), json!(types)),
+        };
+    
+        // An enum only makes sense when strings (and possibly nulls) are all we saw
+        if let Some(values) = &self.string_values {
+            let only_strings = types.iter().all(|t| *t == "string" || *t == "null");
+            if only_strings && self.strings >= values.len() * options.min_enum_repeats {
+                let mut enum_values: Vec<serde_json::Value> = values.iter().map(|v| json!(v)).collect();
+                if self.nulls > 0 {
+                    enum_values.push(serde_json::Value::Null);
+                }
+                schema.insert("enum".to_string(), json!(enum_values));
+            }
+        }
+        if let Some(format) = self.string_format.filter(|_| !schema.contains_key("enum")) {
+            schema.insert("format".to_string(), json!(format.name()));
+        }
+    
+        if let Some(items) = &self.items {
+            let items = items.to_json(options);
+            if items != json!({}) {
+                schema.insert("items".to_string(), items);
+            }
+        }
+    
+        if self.objects > 0 {
+            let properties: Map<String, serde_json::Value> = self
+                .properties
+                .iter()
+                .map(|(key, shape)| (key.clone(), shape.to_json(options)))
+                .collect();
+            let required: Vec<&String> = self
+                .property_counts
+                .iter()
+                .filter(|(_, count)| **count == self.objects)
+                .map(|(key, _)| key)
+                .collect();
+            schema.insert("properties".to_string(), serde_json::Value::Object(properties));
+            if !required.is_empty() {
+                schema.insert("required".to_string(), json!(required));
+            }
+        }
+    
+        serde_json::Value::Object(schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{schema::Schema, toon_parser::ToonParser};
+
+    fn infer(samples: &[&str]) -> serde_json::Value {
+        let mut inferrer = SchemaInferrer::default();
+        for sample in samples {
+            inferrer.add(&ToonParser::parse(sample).unwrap());
+        }
+        inferrer.to_json_schema()
+    }
+
+    #[test]
+    fn test_merges_types_and_required_keys() {
+        let schema = infer(&[
+            "name: \"a\"\nscore: 1\nnote: null",
+            "name: \"b\"\nscore: 2.5",
+        ]);
+    
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["name", "score"]));
+        assert_eq!(schema["properties"]["score"]["type"], "number");
+        assert_eq!(schema["properties"]["note"]["type"], "null");
+        assert!(schema["properties"]["name"].get("enum").is_none());
+    }
+
+    #[test]
+    fn test_string_formats() {
+        let schema = infer(&[
+            "email: ada@example.com\nseen: \"2024-01-05T10:00:00Z\"",
+            "email: bob@example.com\nseen: \"2024-01-06\"",
+        ]);
+    
+        assert_eq!(schema["properties"]["email"]["format"], "email");
+        assert!(schema["properties"]["seen"].get("format").is_none());
+    }
+
+    #[test]
+    fn test_table_columns_and_enums() {
+        let schema = infer(&[r#"users[4]:
+  - id: 1
+    role: "admin"
+  - id: 2
+    role: "user"
+  - id: 3
+    role: "user"
+  - id: 4
+    role: "admin""#]);
+    
+        let row = &schema["properties"]["users"]["items"];
+        assert_eq!(schema["properties"]["users"]["type"], "array");
+        assert_eq!(row["properties"]["id"]["type"], "integer");
+        assert_eq!(row["properties"]["role"]["enum"], json!(["admin", "user"]));
+        assert_eq!(row["required"], json!(["id", "role"]));
+    }
+
+    #[test]
+    fn test_inferred_schema_validates_samples() {
+        let samples = [
+            "tags: [\"x\", \"y\"]\nactive: true",
+            "tags: []\nactive: false\nextra: 1",
+        ];
+        let schema = Schema::from_json(&infer(&samples)).unwrap();
+    
+        for sample in samples {
+            assert!(schema.validate(&ToonParser::parse(sample).unwrap()).is_empty());
+        }
+        assert!(!schema.validate(&ToonParser::parse("active: 1").unwrap()).is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{diagnostic::{Diagnostic, Span}, path};
+use std::collections::HashMap;
+
+/// Maps value paths to where they start in the source text, and keeps the
+/// comments the parser skipped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceMap {
+    spans: HashMap<String, Span>,
+    comments: Vec<Comment>,
+    scalars: Vec<UnquotedScalar>,
+    duplicate_keys: Vec<DuplicateKey>,
+}
+
+/// A full-line `#` comment; `text` excludes the `#` and surrounding whitespace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub span: Span,
+    pub text: String,
+}
+
+/// An unquoted token the parser read as a number, boolean or null, such as
+/// `0012` becoming the number 12.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnquotedScalar {
+    pub path: String,
+    /// Where the token starts
+    pub span: Span,
+    /// The token as written
+    pub text: String,
+}
+
+/// A key written again in the same object or row. The parser keeps the last
+/// value, so earlier ones are lost.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateKey {
+    pub key: String,
+    pub path: String,
+    /// Where the repeated key starts
+    pub span: Span,
+    /// Where the key was first written, when known
+    pub first: Option<Span>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn insert(&mut self, path: String, span: Span) {
+        self.spans.insert(path, span);
+    }
+    
+    pub fn get(&self, path: &str) -> Option<Span> {
+        self.spans.get(path).copied()
+    }
+    
+    /// Finds the span for a path, falling back to its nearest recorded ancestor.
+    pub fn locate(&self, path: &str) -> Option<Span> {
+        let mut current = path;
+        loop {
+            if let Some(span) = self.get(current) {
+                return Some(span);
+            }
+            current = path::parent(current)?;
+        }
+    }
+    
+    /// Fills in missing spans on diagnostics from their paths.
+    pub fn attach(&self, diagnostics: &mut [Diagnostic]) {
+        for diagnostic in diagnostics.iter_mut().filter(|d| d.span.is_none()) {
+            diagnostic.span = self.locate(&diagnostic.path);
+        }
+    }
+    
+    pub fn add_comment(&mut self, comment: Comment) {
+        self.comments.push(comment);
+    }
+    
+    /// Comments in source order.
+    pub fn comments(&self) -> &[Comment] {
+        &self.comments
+    }
+    
+    pub fn add_scalar(&mut self, scalar: UnquotedScalar) {
+        self.scalars.push(scalar);
+    }
+    
+    /// Unquoted tokens that were not read as strings, in source order.
+    pub fn scalars(&self) -> &[UnquotedScalar] {
+        &self.scalars
+    }
+    
+    pub fn add_duplicate_key(&mut self, duplicate: DuplicateKey) {
+        self.duplicate_keys.push(duplicate);
+    }
+    
+    /// Keys repeated within an object or row, in source order.
+    pub fn duplicate_keys(&self) -> &[DuplicateKey] {
+        &self.duplicate_keys
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty() && self.comments.is_empty() && self.scalars.is_empty() && self.duplicate_keys.is_empty()
+    }
+}
@@ -0,0 +1,220 @@
+//! Suppression comments in TOON sources:
+//!
+//! - `# tval-disable-next-line <id>...` silences the next non-comment line
+//! - `# tval-disable <id>...` silences the rest of the file, until
+//! - `# tval-enable <id>...` turns matching suppressions back off
+//!
+//! IDs are rule IDs, names from [`codes::name`] or diagnostic codes; without
+//! IDs every diagnostic is silenced.
+
+use crate::{
+    diagnostic::{codes, Diagnostic, Span},
+    source_map::Comment,
+};
+
+const PREFIX: &str = "tval-";
+
+#[derive(Debug)]
+struct Suppression {
+    /// Where the comment is, for reporting it as unused
+    span: Span,
+    /// `None` matches every diagnostic
+    id: Option<String>,
+    first_line: usize,
+    /// Last suppressed line; `None` runs to the end of the file
+    last_line: Option<usize>,
+    used: bool,
+}
+
+impl Suppression {
+    fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        let covered = match diagnostic.span {
+            Some(span) => {
+                span.line >= self.first_line && self.last_line.is_none_or(|last| span.line <= last)
+            }
+            // Without a position only a suppression covering the whole rest of the file can apply
+            None => self.last_line.is_none(),
+        };
+        covered && match &self.id {
+            None => true,
+            Some(id) => {
+                diagnostic.rule.as_deref() == Some(id.as_str())
+                    || diagnostic.code == *id
+                    || codes::name(&diagnostic.code) == Some(id.as_str())
+            }
+        }
+    }
+    
+    fn describe(&self) -> String {
+        match &self.id {
+            Some(id) => format!("Unused suppression for {}", id),
+            None => "Unused suppression".to_string(),
+        }
+    }
+}
+
+/// The suppressions declared in a document's comments.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    entries: Vec<Suppression>,
+    invalid: Vec<Diagnostic>,
+}
+
+impl Suppressions {
+    pub fn from_comments(comments: &[Comment]) -> Self {
+        let mut suppressions = Suppressions::default();
+        
+        for (idx, comment) in comments.iter().enumerate() {
+            let Some(directive) = comment.text.strip_prefix(PREFIX) else {
+                continue;
+            };
+            let mut words = directive.split_whitespace();
+            let kind = words.next().unwrap_or_default();
+            let ids: Vec<Option<String>> = {
+                let ids: Vec<Option<String>> = words
+                    .flat_map(|w| w.split(','))
+                    .filter(|id| !id.is_empty())
+                    .map(|id| Some(id.to_string()))
+                    .collect();
+                if ids.is_empty() { vec![None] } else { ids }
+            };
+            
+            match kind {
+                "disable-next-line" => {
+                    let target = Self::next_code_line(comments, idx);
+                    for id in ids {
+                        suppressions.push(comment.span, id, target, Some(target));
+                    }
+                }
+                "disable" => {
+                    for id in ids {
+                        suppressions.push(comment.span, id, comment.span.line, None);
+                    }
+                }
+                "enable" => {
+                    let line = comment.span.line;
+                    for entry in suppressions.entries.iter_mut().filter(|e| e.last_line.is_none()) {
+                        if ids.contains(&None) || ids.contains(&entry.id) {
+                            entry.last_line = Some(line);
+                        }
+                    }
+                }
+                _ => suppressions.invalid.push(
+                    Diagnostic::warning(
+                        codes::INVALID_SUPPRESSION,
+                        "",
+                        format!("Unknown suppression directive '{}{}'", PREFIX, kind),
+                    )
+                    .with_span(comment.span),
+                ),
+            }
+        }
+        
+        suppressions
+    }
+    
+    /// The first line after the comment at `idx` that isn't itself a comment.
+    fn next_code_line(comments: &[Comment], idx: usize) -> usize {
+        let mut line = comments[idx].span.line + 1;
+        for comment in &comments[idx + 1..] {
+            if comment.span.line != line {
+                break;
+            }
+            line += 1;
+        }
+        line
+    }
+    
+    fn push(&mut self, span: Span, id: Option<String>, first_line: usize, last_line: Option<usize>) {
+        self.entries.push(Suppression { span, id, first_line, last_line, used: false });
+    }
+    
+    /// Drops suppressed diagnostics and adds warnings for suppressions that
+    /// matched nothing and for malformed directives.
+    pub fn apply(mut self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        let mut kept: Vec<Diagnostic> = diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                let mut suppressed = false;
+                for entry in self.entries.iter_mut().filter(|e| e.matches(diagnostic)) {
+                    entry.used = true;
+                    suppressed = true;
+                }
+                !suppressed
+            })
+            .collect();
+        
+        for entry in self.entries.iter().filter(|e| !e.used) {
+            kept.push(
+                Diagnostic::warning(codes::UNUSED_SUPPRESSION, "", entry.describe())
+                    .with_span(entry.span),
+            );
+        }
+        kept.append(&mut self.invalid);
+        kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toon_parser::ToonParser;
+
+    fn diagnostic(code: &str, rule: Option<&str>, line: usize) -> Diagnostic {
+        let diagnostic = Diagnostic::warning(code, "x", "problem").with_span(Span::new(line, 1));
+        match rule {
+            Some(rule) => diagnostic.with_rule(rule),
+            None => diagnostic,
+        }
+    }
+
+    fn suppressions(input: &str) -> Suppressions {
+        let (_, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        Suppressions::from_comments(source_map.comments())
+    }
+
+    #[test]
+    fn test_disable_next_line() {
+        let suppressions = suppressions(r#"# tval-disable-next-line empty-array
+# another comment
+tags: []
+other: []"#);
+        let kept = suppressions.apply(vec![
+            diagnostic(codes::EMPTY_ARRAY, Some("empty-array"), 3),
+            diagnostic(codes::EMPTY_ARRAY, Some("empty-array"), 4),
+        ]);
+        
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].span, Some(Span::new(4, 1)));
+    }
+
+    #[test]
+    fn test_disable_by_name_and_enable() {
+        let suppressions = suppressions(r#"# tval-disable table-schema
+a: 1
+# tval-enable table-schema
+b: 2"#);
+        let kept = suppressions.apply(vec![
+            diagnostic(codes::TABLE_SCHEMA_INCONSISTENT, None, 2),
+            diagnostic(codes::TABLE_SCHEMA_INCONSISTENT, None, 4),
+        ]);
+        
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].span, Some(Span::new(4, 1)));
+    }
+
+    #[test]
+    fn test_unused_and_invalid_suppressions_are_reported() {
+        let suppressions = suppressions(r#"# tval-disable-next-line empty-object, empty-array
+tags: []
+# tval-disabel empty-array
+a: 1"#);
+        let kept = suppressions.apply(vec![diagnostic(codes::EMPTY_ARRAY, Some("empty-array"), 2)]);
+        let found: Vec<(&str, &str)> = kept.iter().map(|d| (d.code.as_str(), d.message.as_str())).collect();
+        
+        assert_eq!(found, vec![
+            (codes::UNUSED_SUPPRESSION, "Unused suppression for empty-object"),
+            (codes::INVALID_SUPPRESSION, "Unknown suppression directive 'tval-disabel'"),
+        ]);
+    }
+}
@@ -6,7 +6,7 @@ impl TokenEstimator {
     pub fn estimate(value: &Value) -> usize {
         let chars = Self::count_characters(value);
         // Simple heuristic: approximately 4 characters per token
-        (chars + 3) / 4
+        chars.div_ceil(4)
     }
     
     pub fn estimate_breakdown(value: &Value) -> TokenBreakdown {
@@ -15,6 +15,47 @@ impl TokenEstimator {
         breakdown
     }
     
+    /// Estimated tokens per top-level key (or root array item), largest first.
+    pub fn estimate_top_level(value: &Value) -> Vec<(String, usize)> {
+        let mut contributions: Vec<(String, usize)> = match value {
+            Value::Object(obj) => obj
+                .iter()
+                // The key itself plus `"": `
+                .map(|(key, val)| (key.clone(), (key.len() + 4 + Self::count_characters(val)).div_ceil(4)))
+                .collect(),
+            Value::Array(arr) => arr
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| (format!("[{}]", idx), Self::estimate(item)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        contributions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        contributions
+    }
+    
+    /// Estimated tokens for table row fields, each written as `key: value`.
+    pub fn estimate_fields<'a>(fields: impl IntoIterator<Item = (&'a String, &'a Value)>) -> usize {
+        fields
+            .into_iter()
+            .map(|(key, val)| key.len() + 2 + Self::count_characters(val))
+            .sum::<usize>()
+            .div_ceil(4)
+    }
+    
+    /// Returns the overrun when the estimated size is above `budget` tokens.
+    pub fn check_budget(value: &Value, budget: usize) -> Option<BudgetOverrun> {
+        let tokens = Self::estimate(value);
+        if tokens <= budget {
+            return None;
+        }
+        Some(BudgetOverrun {
+            tokens,
+            budget,
+            contributors: Self::estimate_top_level(value),
+        })
+    }
+    
     fn count_characters(value: &Value) -> usize {
         match value {
             Value::Null => 4,
@@ -61,7 +102,7 @@ impl TokenEstimator {
             Value::Null => breakdown.add_primitive(1),
             Value::Bool(_) => breakdown.add_primitive(1),
             Value::Number(_) => breakdown.add_primitive(1),
-            Value::String(s) => breakdown.add_string((s.len() + 3) / 4),
+            Value::String(s) => breakdown.add_string(s.len().div_ceil(4)),
             Value::Array(arr) => {
                 breakdown.add_structure(1);
                 for item in arr {
@@ -71,18 +112,18 @@ impl TokenEstimator {
             Value::Object(obj) => {
                 breakdown.add_structure(obj.len());
                 for (key, val) in obj {
-                    breakdown.add_key((key.len() + 3) / 4);
+                    breakdown.add_key(key.len().div_ceil(4));
                     Self::analyze_value(val, breakdown);
                 }
             }
             Value::Table(table) => {
                 breakdown.add_table(
-                    (table.name.len() + 3) / 4,
+                    table.name.len().div_ceil(4),
                     table.rows.len()
                 );
                 for row in &table.rows {
                     for (key, val) in row {
-                        breakdown.add_key((key.len() + 3) / 4);
+                        breakdown.add_key(key.len().div_ceil(4));
                         Self::analyze_value(val, breakdown);
                     }
                 }
@@ -91,6 +132,46 @@ impl TokenEstimator {
     }
 }
 
+/// A document whose estimated token count is over its budget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetOverrun {
+    pub tokens: usize,
+    pub budget: usize,
+    /// Estimated tokens per top-level key, largest first
+    pub contributors: Vec<(String, usize)>,
+}
+
+impl BudgetOverrun {
+    /// Number of contributors named in the message
+    const SHOWN_CONTRIBUTORS: usize = 3;
+    
+    pub fn excess(&self) -> usize {
+        self.tokens - self.budget
+    }
+    
+    /// e.g. `Estimated 1250 tokens exceeds the budget of 1000 by 250 (25%);
+    /// largest top-level keys: users 900, orders 300`
+    pub fn message(&self) -> String {
+        let mut message = format!(
+            "Estimated {} tokens exceeds the budget of {} by {} ({}%)",
+            self.tokens,
+            self.budget,
+            self.excess(),
+            (self.excess() * 100).div_ceil(self.budget.max(1))
+        );
+        if !self.contributors.is_empty() {
+            let largest: Vec<String> = self
+                .contributors
+                .iter()
+                .take(Self::SHOWN_CONTRIBUTORS)
+                .map(|(key, tokens)| format!("{} {}", key, tokens))
+                .collect();
+            message.push_str(&format!("; largest top-level keys: {}", largest.join(", ")));
+        }
+        message
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenBreakdown {
     pub keys: usize,
@@ -101,6 +182,12 @@ pub struct TokenBreakdown {
     pub table_rows: usize,
 }
 
+impl Default for TokenBreakdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TokenBreakdown {
     pub fn new() -> Self {
         TokenBreakdown {
@@ -231,6 +318,24 @@ mod tests {
         assert_eq!(breakdown.table_rows, 2); // 2 rows
     }
 
+    #[test]
+    fn test_check_budget() {
+        let mut obj = HashMap::new();
+        obj.insert("notes".to_string(), Value::String("x".repeat(60)));
+        obj.insert("id".to_string(), Value::Number(1.0));
+        obj.insert("tags".to_string(), Value::Array(vec![Value::String("a".to_string())]));
+        let value = Value::Object(obj);
+        let tokens = TokenEstimator::estimate(&value);
+        
+        assert!(TokenEstimator::check_budget(&value, tokens).is_none());
+        let overrun = TokenEstimator::check_budget(&value, 10).unwrap();
+        assert_eq!(overrun.excess(), tokens - 10);
+        let keys: Vec<&str> = overrun.contributors.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["notes", "tags", "id"]);
+        assert!(overrun.message().starts_with(&format!("Estimated {} tokens exceeds the budget of 10 by {}", tokens, tokens - 10)));
+        assert!(overrun.message().ends_with("; largest top-level keys: notes 18, tags 4, id 2"));
+    }
+
     #[test]
     fn test_breakdown_total() {
         let breakdown = TokenBreakdown {
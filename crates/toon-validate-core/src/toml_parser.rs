@@ -0,0 +1,130 @@
+use crate::{
+    error::{Result, TqError},
+    value::{Table, Value},
+};
+use std::collections::HashMap;
+
+pub struct TomlParser;
+
+impl TomlParser {
+    pub fn parse(input: &str) -> Result<Value> {
+        let root: toml::Table = input.parse().map_err(|e: toml::de::Error| {
+            let (line, column) = e
+                .span()
+                .map(|span| Self::line_column(input, span.start))
+                .unwrap_or((1, 1));
+            TqError::Syntax {
+                line,
+                column,
+                message: e.message().to_string(),
+            }
+        })?;
+        
+        Ok(Value::Object(Self::convert_table(root)))
+    }
+    
+    fn convert_table(table: toml::Table) -> HashMap<String, Value> {
+        table
+            .into_iter()
+            .map(|(key, value)| {
+                let converted = Self::convert_toml_to_value(&key, value);
+                (key, converted)
+            })
+            .collect()
+    }
+    
+    fn convert_toml_to_value(key: &str, value: toml::Value) -> Value {
+        match value {
+            toml::Value::String(s) => Value::String(s),
+            toml::Value::Integer(i) => Value::Number(i as f64),
+            toml::Value::Float(f) => Value::Number(f),
+            toml::Value::Boolean(b) => Value::Bool(b),
+            toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+            // Arrays of tables (`[[tools]]`) are TOON tables
+            toml::Value::Array(arr) if !arr.is_empty() && arr.iter().all(toml::Value::is_table) => {
+                let rows: Vec<HashMap<String, Value>> = arr
+                    .into_iter()
+                    .filter_map(|item| match item {
+                        toml::Value::Table(t) => Some(Self::convert_table(t)),
+                        _ => None,
+                    })
+                    .collect();
+                Value::Table(Table {
+                    name: key.to_string(),
+                    declared_rows: rows.len(),
+                    rows,
+                })
+            }
+            toml::Value::Array(arr) => Value::Array(
+                arr.into_iter()
+                    .map(|item| Self::convert_toml_to_value(key, item))
+                    .collect(),
+            ),
+            toml::Value::Table(t) => Value::Object(Self::convert_table(t)),
+        }
+    }
+    
+    fn line_column(input: &str, offset: usize) -> (usize, usize) {
+        let before = &input[..offset.min(input.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+        (line, column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scalars_and_tables() {
+        let input = r#"name = "agent"
+version = 2
+ratio = 0.5
+enabled = true
+released = 2024-05-01
+
+[limits]
+max_tokens = 4096
+"#;
+        let result = TomlParser::parse(input).unwrap();
+        let obj = result.as_object().unwrap();
+        
+        assert_eq!(obj.get("name"), Some(&Value::String("agent".to_string())));
+        assert_eq!(obj.get("version"), Some(&Value::Number(2.0)));
+        assert_eq!(obj.get("ratio"), Some(&Value::Number(0.5)));
+        assert_eq!(obj.get("enabled"), Some(&Value::Bool(true)));
+        assert_eq!(obj.get("released"), Some(&Value::String("2024-05-01".to_string())));
+        
+        let limits = obj.get("limits").and_then(Value::as_object).unwrap();
+        assert_eq!(limits.get("max_tokens"), Some(&Value::Number(4096.0)));
+    }
+
+    #[test]
+    fn test_array_of_tables_becomes_table() {
+        let input = r#"[[tools]]
+name = "search"
+args = ["query"]
+
+[[tools]]
+name = "fetch"
+args = ["url"]
+"#;
+        let result = TomlParser::parse(input).unwrap();
+        let table = result.get_path("tools").and_then(Value::as_table).unwrap();
+        
+        assert_eq!(table.name, "tools");
+        assert_eq!(table.declared_rows, 2);
+        assert_eq!(table.rows[1].get("name"), Some(&Value::String("fetch".to_string())));
+        assert_eq!(
+            table.rows[0].get("args"),
+            Some(&Value::Array(vec![Value::String("query".to_string())]))
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_position() {
+        let result = TomlParser::parse("a = 1\nb = \n");
+        assert!(matches!(result, Err(TqError::Syntax { line: 2, .. })));
+    }
+}
@@ -1,52 +1,104 @@
-use crate::{error::{Result, TqError}, value::{Table, Value}};
+use crate::{
+    diagnostic::Span,
+    error::{Result, TqError},
+    path,
+    source_map::{Comment, DuplicateKey, SourceMap, UnquotedScalar},
+    value::{Table, Value},
+};
 use std::collections::HashMap;
 
 pub struct ToonParser {
     lines: Vec<String>,
     current: usize,
+    source_map: SourceMap,
+    /// Fail on tables whose row count disagrees with their header
+    strict_row_counts: bool,
 }
 
 impl ToonParser {
     pub fn parse(input: &str) -> Result<Value> {
+        Self::run(input, true).map(|(value, _)| value)
+    }
+    
+    /// Parses the input and records where each key, table and row starts,
+    /// along with every comment line and every unquoted token that was read
+    /// as a number, boolean or null.
+    ///
+    /// Unlike `parse`, a table whose row count disagrees with its header is
+    /// returned as is, so the validator can report it with a position and a fix.
+    pub fn parse_with_source_map(input: &str) -> Result<(Value, SourceMap)> {
+        Self::run(input, false)
+    }
+    
+    fn run(input: &str, strict_row_counts: bool) -> Result<(Value, SourceMap)> {
         let mut parser = ToonParser {
             lines: input.lines().map(String::from).collect(),
             current: 0,
+            source_map: SourceMap::new(),
+            strict_row_counts,
         };
-        parser.parse_value(0)
+        parser.collect_comments();
+        let value = parser.parse_value(0, "")?;
+        Ok((value, parser.source_map))
+    }
+    
+    fn collect_comments(&mut self) {
+        for (idx, line) in self.lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if let Some(text) = trimmed.strip_prefix('#') {
+                let column = line.chars().count() - trimmed.chars().count() + 1;
+                self.source_map.add_comment(Comment {
+                    span: Span::new(idx + 1, column),
+                    text: text.trim().to_string(),
+                });
+            }
+        }
     }
     
-    fn parse_value(&mut self, indent: usize) -> Result<Value> {
+    fn parse_value(&mut self, indent: usize, path: &str) -> Result<Value> {
         let mut obj = HashMap::new();
         
         while self.current < self.lines.len() {
             let line = &self.lines[self.current];
             let line_indent = Self::count_indent(line);
             
+            // Blank lines and comments may be indented any amount
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                self.current += 1;
+                continue;
+            }
+            
             if line_indent < indent {
                 break;
             }
             
+            // Deeper lines are consumed by the key or table above them, so
+            // one left over here belongs to nothing
             if line_indent > indent {
-                continue;
-            }
-            
-            let trimmed = line.trim();
-            if trimmed.is_empty() || trimmed.starts_with('#') {
-                self.current += 1;
-                continue;
+                return Err(TqError::Parse {
+                    line: self.current + 1,
+                    message: format!("Unexpected indentation: {}", trimmed),
+                });
             }
             
             if let Some(table_match) = Self::parse_table_header(trimmed) {
+                let table_path = path::join_key(path, &table_match.0);
+                self.record_duplicate(&obj, &table_match.0, &table_path, Span::new(self.current + 1, line_indent + 1));
+                self.source_map.insert(table_path.clone(), Span::new(self.current + 1, line_indent + 1));
                 self.current += 1;
-                let table = self.parse_table(table_match.0, table_match.1, indent)?;
+                let table = self.parse_table(table_match.0, table_match.1, indent, &table_path)?;
                 obj.insert(table.name.clone(), Value::Table(table));
             } else if let Some((key, value)) = Self::parse_key_value(trimmed) {
+                let key_path = path::join_key(path, &key);
+                self.record_duplicate(&obj, &key, &key_path, Span::new(self.current + 1, line_indent + 1));
+                self.source_map.insert(key_path.clone(), Span::new(self.current + 1, line_indent + 1));
+                self.record_scalars(&key_path);
                 self.current += 1;
                 // Check if this is a nested object
-                if matches!(value, Value::Null) && self.current < self.lines.len() {
-                    let next_line_indent = Self::count_indent(&self.lines[self.current]);
-                    if next_line_indent > indent {
-                        let nested_value = self.parse_value(indent + 2)?;
+                if matches!(value, Value::Null) {
+                    if self.next_content_line().is_some_and(|next| Self::count_indent(next) > indent) {
+                        let nested_value = self.parse_value(indent + 2, &key_path)?;
                         obj.insert(key, nested_value);
                     } else {
                         obj.insert(key, value);
@@ -65,54 +117,80 @@ impl ToonParser {
         Ok(Value::Object(obj))
     }
     
-    fn parse_table(&mut self, name: String, declared_rows: usize, parent_indent: usize) -> Result<Table> {
+    fn parse_table(
+        &mut self,
+        name: String,
+        declared_rows: usize,
+        parent_indent: usize,
+        table_path: &str,
+    ) -> Result<Table> {
         let mut rows = Vec::new();
         let expected_indent = parent_indent + 2;
+        // Lines of rows beyond the declared count
+        let mut extra_lines = Vec::new();
         
-        while self.current < self.lines.len() && rows.len() < declared_rows {
+        while self.current < self.lines.len() && (rows.len() < declared_rows || self.row_ahead(expected_indent)) {
             let line = &self.lines[self.current];
             let line_indent = Self::count_indent(line);
             
-            if line_indent < expected_indent {
-                break;
-            }
-            
+            // Blank lines and comments don't end the table
             let trimmed = line.trim();
             if trimmed.is_empty() || trimmed.starts_with('#') {
                 self.current += 1;
                 continue;
             }
             
-            if trimmed.starts_with("- ") {
+            if line_indent < expected_indent {
+                break;
+            }
+            
+            // A bare `-` starts a row whose fields are all on following lines
+            let row_marker = if trimmed == "-" { Some("") } else { trimmed.strip_prefix("- ") };
+            if let Some(after_dash) = row_marker {
+                let row_path = path::join_index(table_path, rows.len());
+                self.source_map.insert(row_path.clone(), Span::new(self.current + 1, line_indent + 1));
+                if rows.len() >= declared_rows {
+                    extra_lines.push(self.current + 1);
+                }
                 // Check if there's a field on the same line as the dash
-                let after_dash = &trimmed[2..];
                 if !after_dash.is_empty() {
                     // Parse the field on the same line
                     let mut row = HashMap::new();
-                    if let Some((key, value)) = Self::parse_key_value(after_dash) {
-                        row.insert(key, value);
-                    }
+                    let (key, value) = Self::parse_key_value(after_dash)
+                        .ok_or_else(|| self.invalid_field(&name, rows.len(), after_dash))?;
+                    let key_path = path::join_key(&row_path, &key);
+                    self.source_map.insert(key_path.clone(), Span::new(self.current + 1, line_indent + 3));
+                    self.record_scalars(&key_path);
+                    row.insert(key, value);
                     self.current += 1;
                     // Then parse any additional fields on following lines
-                    let additional_fields = self.parse_table_row(expected_indent + 2)?;
-                    row.extend(additional_fields);
+                    let row = self.parse_table_row(row, expected_indent + 2, &name, rows.len(), &row_path)?;
                     rows.push(row);
                 } else {
                     // Dash is on its own line, fields are on following lines
                     self.current += 1;
-                    let row = self.parse_table_row(expected_indent + 2)?;
+                    let row = self.parse_table_row(HashMap::new(), expected_indent + 2, &name, rows.len(), &row_path)?;
                     rows.push(row);
                 }
             } else {
-                self.current += 1;
+                return Err(TqError::Parse {
+                    line: self.current + 1,
+                    message: format!(
+                        "Table {} row {}: expected a row starting with '- ', found: {}",
+                        name,
+                        rows.len() + 1,
+                        trimmed
+                    ),
+                });
             }
         }
         
-        if rows.len() != declared_rows {
+        if self.strict_row_counts && rows.len() != declared_rows {
             return Err(TqError::TableRowMismatch {
                 name: name.clone(),
                 declared: declared_rows,
                 actual: rows.len(),
+                extra_lines,
             });
         }
         
@@ -123,36 +201,67 @@ impl ToonParser {
         })
     }
     
-    fn parse_table_row(&mut self, indent: usize) -> Result<HashMap<String, Value>> {
-        let mut row = HashMap::new();
-        
+    /// Whether the next line that isn't blank or a comment starts a row at
+    /// `indent`, i.e. another row of the table being parsed.
+    fn row_ahead(&self, indent: usize) -> bool {
+        self.next_content_line().is_some_and(|line| {
+            let trimmed = line.trim();
+            Self::count_indent(line) == indent && (trimmed == "-" || trimmed.starts_with("- "))
+        })
+    }
+    
+    /// The next line from the current one that isn't blank or a comment.
+    fn next_content_line(&self) -> Option<&str> {
+        self.lines[self.current..]
+            .iter()
+            .map(|line| line.as_str())
+            .find(|line| {
+                let trimmed = line.trim();
+                !trimmed.is_empty() && !trimmed.starts_with('#')
+            })
+    }
+    
+    /// Adds the fields of row `index` (0-based) that follow its `-` line to
+    /// `row`, which holds any field written on the `-` line itself.
+    fn parse_table_row(
+        &mut self,
+        mut row: HashMap<String, Value>,
+        indent: usize,
+        table_name: &str,
+        index: usize,
+        row_path: &str,
+    ) -> Result<HashMap<String, Value>> {
         while self.current < self.lines.len() {
             let line = &self.lines[self.current];
             let line_indent = Self::count_indent(line);
             
-            // Stop if we've gone back to a less indented line
-            if line_indent < indent {
-                break;
-            }
-            
             let trimmed = line.trim();
             if trimmed.is_empty() || trimmed.starts_with('#') {
                 self.current += 1;
                 continue;
             }
             
+            // Stop if we've gone back to a less indented line
+            if line_indent < indent {
+                break;
+            }
+            
             // Stop if we hit another row marker
-            if trimmed.starts_with("- ") {
+            if trimmed == "-" || trimmed.starts_with("- ") {
                 break;
             }
             
             // Only parse fields at the expected indent level
             if line_indent == indent {
                 if let Some((key, value)) = Self::parse_key_value(trimmed) {
+                    let key_path = path::join_key(row_path, &key);
+                    self.record_duplicate(&row, &key, &key_path, Span::new(self.current + 1, line_indent + 1));
+                    self.source_map.insert(key_path.clone(), Span::new(self.current + 1, line_indent + 1));
+                    self.record_scalars(&key_path);
                     self.current += 1;
                     row.insert(key, value);
                 } else {
-                    break;
+                    return Err(self.invalid_field(table_name, index, trimmed));
                 }
             } else {
                 // Skip lines that are more indented (could be nested structures)
@@ -163,6 +272,63 @@ impl ToonParser {
         Ok(row)
     }
     
+    /// Records the unquoted tokens in the value of the current `key: value`
+    /// line, including the items of an inline array.
+    fn record_scalars(&mut self, key_path: &str) {
+        let line = &self.lines[self.current];
+        let Some(colon_pos) = line.find(':') else {
+            return;
+        };
+        let after_colon = &line[colon_pos + 1..];
+        let start = colon_pos + 1 + (after_colon.len() - after_colon.trim_start().len());
+        let text = line[start..].trim_end();
+        
+        let mut tokens = Vec::new();
+        match text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            Some(items) => {
+                let mut offset = start + 1;
+                for (idx, item) in items.split(',').enumerate() {
+                    let leading = item.len() - item.trim_start().len();
+                    tokens.push((path::join_index(key_path, idx), offset + leading, item.trim()));
+                    offset += item.len() + 1;
+                }
+            }
+            None => tokens.push((key_path.to_string(), start, text)),
+        }
+        
+        let scalars: Vec<UnquotedScalar> = tokens
+            .into_iter()
+            .filter(|(_, _, token)| {
+                !token.is_empty() && !matches!(Self::parse_simple_value(token), Value::String(_) | Value::Array(_))
+            })
+            .map(|(path, byte_offset, token)| UnquotedScalar {
+                path,
+                span: Span::new(self.current + 1, line[..byte_offset].chars().count() + 1),
+                text: token.to_string(),
+            })
+            .collect();
+        for scalar in scalars {
+            self.source_map.add_scalar(scalar);
+        }
+    }
+    
+    /// Records `key` as repeated when `fields` already holds it. Must run
+    /// before the key's new span replaces the first one.
+    fn record_duplicate(&mut self, fields: &HashMap<String, Value>, key: &str, key_path: &str, span: Span) {
+        if fields.contains_key(key) {
+            let first = self.source_map.get(key_path);
+            self.source_map.add_duplicate_key(DuplicateKey { key: key.to_string(), path: key_path.to_string(), span, first });
+        }
+    }
+    
+    /// A row field on the current line that isn't `key: value`.
+    fn invalid_field(&self, table_name: &str, index: usize, field: &str) -> TqError {
+        TqError::Parse {
+            line: self.current + 1,
+            message: format!("Table {} row {}: expected 'key: value', found: {}", table_name, index + 1, field.trim()),
+        }
+    }
+    
     fn parse_table_header(line: &str) -> Option<(String, usize)> {
         if let Some(bracket_pos) = line.find('[') {
             if let Some(colon_pos) = line.rfind(':') {
@@ -221,6 +387,9 @@ impl ToonParser {
         
         if trimmed.starts_with('[') && trimmed.ends_with(']') {
             let array_content = &trimmed[1..trimmed.len() - 1];
+            if array_content.trim().is_empty() {
+                return Value::Array(Vec::new());
+            }
             let items: Vec<Value> = array_content
                 .split(',')
                 .map(|item| Self::parse_simple_value(item.trim()))
@@ -317,7 +486,8 @@ empty: null"#;
     #[test]
     fn test_parse_array() {
         let input = r#"tags: ["rust", "cli", "tool"]
-numbers: [1, 2, 3]"#;
+numbers: [1, 2, 3]
+empty: []"#;
         let result = ToonParser::parse(input).unwrap();
         
         if let Value::Object(obj) = result {
@@ -338,6 +508,8 @@ numbers: [1, 2, 3]"#;
             } else {
                 panic!("Expected numbers array");
             }
+            
+            assert_eq!(obj.get("empty"), Some(&Value::Array(vec![])));
         } else {
             panic!("Expected object");
         }
@@ -358,6 +530,60 @@ active: true"#;
         } else {
             panic!("Expected object");
         }
+        
+        let (_, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        let comments: Vec<(usize, &str)> = source_map
+            .comments()
+            .iter()
+            .map(|c| (c.span.line, c.text.as_str()))
+            .collect();
+        assert_eq!(comments, vec![(1, "This is a comment"), (3, "Another comment")]);
+    }
+
+    #[test]
+    fn test_source_map_records_positions() {
+        let input = r#"name: "test"
+settings:
+  theme: "dark"
+users[2]:
+  - id: 1
+    name: "Alice"
+  - 
+    id: 2"#;
+        let (_, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        
+        assert_eq!(source_map.get("name"), Some(Span::new(1, 1)));
+        assert_eq!(source_map.get("settings.theme"), Some(Span::new(3, 3)));
+        assert_eq!(source_map.get("users"), Some(Span::new(4, 1)));
+        assert_eq!(source_map.get("users[0]"), Some(Span::new(5, 3)));
+        assert_eq!(source_map.get("users[0].id"), Some(Span::new(5, 5)));
+        assert_eq!(source_map.get("users[0].name"), Some(Span::new(6, 5)));
+        assert_eq!(source_map.get("users[1].id"), Some(Span::new(8, 5)));
+        assert_eq!(source_map.locate("users[1].missing"), Some(Span::new(7, 3)));
+    }
+
+    #[test]
+    fn test_source_map_records_unquoted_scalars() {
+        let input = r#"code: 0012
+name: "0012"
+flags: [true, "x", 1e5]
+users[1]:
+  - id: 7
+    nick: null"#;
+        let (_, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        let scalars: Vec<(&str, Span, &str)> = source_map
+            .scalars()
+            .iter()
+            .map(|s| (s.path.as_str(), s.span, s.text.as_str()))
+            .collect();
+        
+        assert_eq!(scalars, vec![
+            ("code", Span::new(1, 7), "0012"),
+            ("flags[0]", Span::new(3, 9), "true"),
+            ("flags[2]", Span::new(3, 20), "1e5"),
+            ("users[0].id", Span::new(5, 9), "7"),
+            ("users[0].nick", Span::new(6, 11), "null"),
+        ]);
     }
 
     #[test]
@@ -375,4 +601,79 @@ active: true"#;
             assert!(error_msg.contains("declared with 3 rows but found 2"));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_surplus_rows() {
+        let input = r#"users[1]:
+  - id: 1
+  - id: 2
+
+  # trailing rows
+  - id: 3
+count: 3"#;
+        match ToonParser::parse(input) {
+            Err(e @ TqError::TableRowMismatch { .. }) => {
+                assert_eq!(e.to_string(), "Table users declared with 1 rows but found 3; extra rows at lines 3, 6");
+                assert_eq!(e.span(), Some(Span::new(3, 1)));
+            }
+            other => panic!("Expected a row count mismatch, got {:?}", other),
+        }
+        
+        let (value, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        assert_eq!(value.get_path("users").and_then(Value::as_table).map(|t| t.rows.len()), Some(3));
+        assert_eq!(value.get_path("count"), Some(&Value::Number(3.0)));
+        assert_eq!(source_map.get("users[2]"), Some(Span::new(6, 3)));
+    }
+
+    #[test]
+    fn test_malformed_row_fields_are_errors() {
+        let cases = [
+            ("users[1]:\n  - id 1", 2, "Table users row 1: expected 'key: value', found: id 1"),
+            ("users[2]:\n  - id: 1\n    name \"Alice\"\n  - id: 2", 3, "Table users row 1: expected 'key: value', found: name \"Alice\""),
+            ("users[2]:\n  - id: 1\n  oops\n  - id: 2", 3, "Table users row 2: expected a row starting with '- ', found: oops"),
+            ("name: \"x\"\n    stray: 1", 2, "Unexpected indentation: stray: 1"),
+        ];
+        
+        for (input, expected_line, expected_message) in cases {
+            for result in [ToonParser::parse(input).map(|_| ()), ToonParser::parse_with_source_map(input).map(|_| ())] {
+                match result {
+                    Err(TqError::Parse { line, message }) => {
+                        assert_eq!(line, expected_line, "{}", input);
+                        assert_eq!(message, expected_message);
+                    }
+                    other => panic!("Expected a parse error for {:?}, got {:?}", input, other),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_indented_blank_lines_and_comments() {
+        let input = "a: 1\n    \n    # note\nb:\n\n  c: 2\n\n  d: 3\nusers[1]:\n  - id: 1\n\n    name: \"Ada\"";
+        let value = ToonParser::parse(input).unwrap();
+        
+        assert_eq!(value.get_path("a"), Some(&Value::Number(1.0)));
+        assert_eq!(value.get_path("b.c"), Some(&Value::Number(2.0)));
+        assert_eq!(value.get_path("b.d"), Some(&Value::Number(3.0)));
+        let users = value.get_path("users").and_then(Value::as_table).unwrap();
+        assert_eq!(users.rows[0].get("name"), Some(&Value::String("Ada".to_string())));
+        assert!(ToonParser::parse_with_source_map(input).is_ok());
+    }
+
+    #[test]
+    fn test_records_duplicate_keys() {
+        let input = "a: 1\na: 2\nusers[1]:\n  - id: 1\n    id: 2\n";
+        let (value, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        
+        assert_eq!(value.get_path("a"), Some(&Value::Number(2.0)));
+        let duplicates: Vec<(&str, Span, Option<Span>)> = source_map
+            .duplicate_keys()
+            .iter()
+            .map(|d| (d.path.as_str(), d.span, d.first))
+            .collect();
+        assert_eq!(duplicates, vec![
+            ("a", Span::new(2, 1), Some(Span::new(1, 1))),
+            ("users[0].id", Span::new(5, 5), Some(Span::new(4, 5))),
+        ]);
+    }
+}
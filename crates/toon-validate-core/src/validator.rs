@@ -1,58 +1,303 @@
 use crate::{
+    diagnostic::{codes, Diagnostic},
     error::{Result, TqError},
+    path,
+    rules::{RuleLevel, RuleOverride, RuleRegistry},
+    source_map::SourceMap,
     value::{Table, Value},
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
 
-pub struct Validator;
+/// Validates documents. The associated functions use the default options;
+/// [`Validator::builder`] makes a validator with a profile, options and rules.
+pub struct Validator {
+    options: ValidatorOptions,
+    rules: RuleRegistry,
+}
+
+/// How strictly values in a table column must share one type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnTypePolicy {
+    /// Count strings such as `"42"` as numbers, so they match numeric columns
+    pub numeric_strings_as_numbers: bool,
+    /// Allow `null` in a column of any type
+    pub allow_null: bool,
+}
+
+impl Default for ColumnTypePolicy {
+    fn default() -> Self {
+        ColumnTypePolicy {
+            numeric_strings_as_numbers: false,
+            allow_null: true,
+        }
+    }
+}
+
+/// Named sets of [`ValidatorOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationProfile {
+    /// Also fails on empty tables and duplicate keys
+    Strict,
+    #[default]
+    Default,
+    /// Allows rows with missing columns and numeric strings in number columns,
+    /// turns structure warnings into notes and column format checks off
+    Lenient,
+}
+
+impl FromStr for ValidationProfile {
+    type Err = TqError;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "strict" => Ok(ValidationProfile::Strict),
+            "default" => Ok(ValidationProfile::Default),
+            "lenient" => Ok(ValidationProfile::Lenient),
+            other => Err(TqError::InvalidConfig(format!(
+                "unknown profile '{}', expected strict, default or lenient",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ValidationProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationProfile::Strict => write!(f, "strict"),
+            ValidationProfile::Default => write!(f, "default"),
+            ValidationProfile::Lenient => write!(f, "lenient"),
+        }
+    }
+}
+
+/// What the validator accepts.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidatorOptions {
+    pub column_types: ColumnTypePolicy,
+    /// Let table rows leave out columns that other rows have, as if they
+    /// were null
+    pub allow_missing_columns: bool,
+    /// Lint rule levels applied on top of the rules' defaults
+    pub rule_levels: Vec<RuleOverride>,
+}
+
+impl ValidatorOptions {
+    pub fn strict() -> Self {
+        ValidatorOptions {
+            rule_levels: Self::levels(&[
+                ("empty-table", RuleLevel::Error),
+                ("repeated-key", RuleLevel::Error),
+                ("unique-keys", RuleLevel::Error),
+            ]),
+            ..Default::default()
+        }
+    }
+    
+    pub fn lenient() -> Self {
+        ValidatorOptions {
+            column_types: ColumnTypePolicy {
+                numeric_strings_as_numbers: true,
+                allow_null: true,
+            },
+            allow_missing_columns: true,
+            rule_levels: Self::levels(&[
+                ("empty-object", RuleLevel::Info),
+                ("empty-array", RuleLevel::Info),
+                ("empty-table", RuleLevel::Info),
+                ("column-format", RuleLevel::Off),
+            ]),
+        }
+    }
+    
+    pub fn for_profile(profile: ValidationProfile) -> Self {
+        match profile {
+            ValidationProfile::Strict => Self::strict(),
+            ValidationProfile::Default => Self::default(),
+            ValidationProfile::Lenient => Self::lenient(),
+        }
+    }
+    
+    /// Sets the configured rule levels on `rules`.
+    pub fn apply_rule_levels(&self, rules: &mut RuleRegistry) -> Result<()> {
+        for level in &self.rule_levels {
+            rules.apply_override(level)?;
+        }
+        Ok(())
+    }
+    
+    fn levels(levels: &[(&str, RuleLevel)]) -> Vec<RuleOverride> {
+        levels
+            .iter()
+            .map(|(id, level)| RuleOverride { id: id.to_string(), level: *level })
+            .collect()
+    }
+}
+
+/// Builds a [`Validator`]; see [`Validator::builder`].
+#[derive(Default)]
+pub struct ValidatorBuilder {
+    options: ValidatorOptions,
+    rules: Option<RuleRegistry>,
+}
+
+impl ValidatorBuilder {
+    /// Starts from a preset, replacing any options set so far.
+    pub fn profile(mut self, profile: ValidationProfile) -> Self {
+        self.options = ValidatorOptions::for_profile(profile);
+        self
+    }
+    
+    pub fn options(mut self, options: ValidatorOptions) -> Self {
+        self.options = options;
+        self
+    }
+    
+    pub fn column_types(mut self, policy: ColumnTypePolicy) -> Self {
+        self.options.column_types = policy;
+        self
+    }
+    
+    pub fn allow_missing_columns(mut self, allow: bool) -> Self {
+        self.options.allow_missing_columns = allow;
+        self
+    }
+    
+    pub fn rule_level(mut self, id: &str, level: RuleLevel) -> Self {
+        self.options.rule_levels.push(RuleOverride { id: id.to_string(), level });
+        self
+    }
+    
+    /// The lint rules to run instead of the defaults. The options' rule
+    /// levels are applied on top of them.
+    pub fn rules(mut self, rules: RuleRegistry) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+    
+    /// Fails when a rule level names an unknown rule.
+    pub fn build(self) -> Result<Validator> {
+        let mut rules = self.rules.unwrap_or_else(RuleRegistry::with_defaults);
+        self.options.apply_rule_levels(&mut rules)?;
+        Ok(Validator { options: self.options, rules })
+    }
+}
 
 impl Validator {
+    pub fn builder() -> ValidatorBuilder {
+        ValidatorBuilder::default()
+    }
+    
+    pub fn options(&self) -> &ValidatorOptions {
+        &self.options
+    }
+    
+    pub fn rules(&self) -> &RuleRegistry {
+        &self.rules
+    }
+    
+    /// Returns every error and lint finding for the document.
+    pub fn check(&self, value: &Value) -> Vec<Diagnostic> {
+        Self::diagnose(value, &self.options, &self.rules)
+    }
+    
     pub fn validate(value: &Value) -> Result<()> {
+        Self::validate_with_policy(value, &ColumnTypePolicy::default())
+    }
+    
+    /// Returns the first validation error, if any.
+    pub fn validate_with_policy(value: &Value, policy: &ColumnTypePolicy) -> Result<()> {
+        let options = ValidatorOptions { column_types: *policy, ..Default::default() };
+        let mut errors = Vec::new();
+        Self::collect_errors(value, "", &options, &mut errors);
+        match errors.into_iter().next() {
+            Some((_, error)) => Err(error),
+            None => Ok(()),
+        }
+    }
+    
+    pub fn validate_all(value: &Value) -> Vec<Diagnostic> {
+        Self::validate_all_with_policy(value, &ColumnTypePolicy::default())
+    }
+    
+    /// Walks the whole tree and returns every error and structural warning.
+    pub fn validate_all_with_policy(value: &Value, policy: &ColumnTypePolicy) -> Vec<Diagnostic> {
+        Self::validate_all_with_rules(value, policy, &RuleRegistry::with_defaults())
+    }
+    
+    /// Like `validate_all_with_policy`, running the given lint rules instead of the defaults.
+    pub fn validate_all_with_rules(
+        value: &Value,
+        policy: &ColumnTypePolicy,
+        rules: &RuleRegistry,
+    ) -> Vec<Diagnostic> {
+        let options = ValidatorOptions { column_types: *policy, ..Default::default() };
+        Self::diagnose(value, &options, rules)
+    }
+    
+    fn diagnose(value: &Value, options: &ValidatorOptions, rules: &RuleRegistry) -> Vec<Diagnostic> {
+        let mut errors = Vec::new();
+        Self::collect_errors(value, "", options, &mut errors);
+        
+        let mut diagnostics: Vec<Diagnostic> = errors
+            .into_iter()
+            .map(|(path, error)| Diagnostic::error(error.code(), &path, error.to_string()))
+            .collect();
+        
+        diagnostics.extend(rules.run(value));
+        diagnostics
+    }
+    
+    fn collect_errors(value: &Value, path: &str, options: &ValidatorOptions, errors: &mut Vec<(String, TqError)>) {
         match value {
-            Value::Object(obj) => Self::validate_object(obj),
-            Value::Table(table) => Self::validate_table(table),
+            Value::Object(obj) => {
+                // Sorted so diagnostics come out in a stable order
+                let mut keys: Vec<&String> = obj.keys().collect();
+                keys.sort();
+                for key in keys {
+                    Self::collect_errors(&obj[key], &path::join_key(path, key), options, errors);
+                }
+            }
+            Value::Table(table) => Self::collect_table_errors(table, path, options, errors),
             Value::Array(arr) => {
-                for item in arr {
-                    Self::validate(item)?;
+                for (idx, item) in arr.iter().enumerate() {
+                    Self::collect_errors(item, &path::join_index(path, idx), options, errors);
                 }
-                Ok(())
             }
-            _ => Ok(()),
+            _ => {}
         }
     }
     
-    fn validate_object(obj: &HashMap<String, Value>) -> Result<()> {
-        for value in obj.values() {
-            Self::validate(value)?;
-        }
-        Ok(())
-    }
-    
-    fn validate_table(table: &Table) -> Result<()> {
+    fn collect_table_errors(
+        table: &Table,
+        path: &str,
+        options: &ValidatorOptions,
+        errors: &mut Vec<(String, TqError)>,
+    ) {
         // Check row count matches declaration
         if table.rows.len() != table.declared_rows {
-            return Err(TqError::TableRowMismatch {
+            errors.push((path.to_string(), TqError::TableRowMismatch {
                 name: table.name.clone(),
                 declared: table.declared_rows,
                 actual: table.rows.len(),
-            });
+                extra_lines: Vec::new(),
+            }));
         }
         
-        // Check schema consistency across rows
-        if !table.rows.is_empty() {
-            let mut schemas: Vec<HashSet<String>> = Vec::new();
-            
-            for row in &table.rows {
-                let schema: HashSet<String> = row.keys().cloned().collect();
-                schemas.push(schema);
-            }
+        // Check schema consistency across rows; rows may differ when missing
+        // columns count as null
+        if let Some(first_row) = table.rows.first().filter(|_| !options.allow_missing_columns) {
+            let first_schema: HashSet<&String> = first_row.keys().collect();
             
-            // Check if all rows have the same set of keys
-            let first_schema = &schemas[0];
-            for (idx, schema) in schemas.iter().enumerate().skip(1) {
+            for (idx, row) in table.rows.iter().enumerate().skip(1) {
+                let schema: HashSet<&String> = row.keys().collect();
                 if schema != first_schema {
-                    let missing: Vec<_> = first_schema.difference(schema).collect();
-                    let extra: Vec<_> = schema.difference(first_schema).collect();
+                    let mut missing: Vec<_> = first_schema.difference(&schema).collect();
+                    let mut extra: Vec<_> = schema.difference(&first_schema).collect();
+                    missing.sort();
+                    extra.sort();
                     
                     let mut message = format!("Row {} has different schema. ", idx + 1);
                     if !missing.is_empty() {
@@ -62,73 +307,143 @@ impl Validator {
                         message.push_str(&format!("Extra fields: {:?}. ", extra));
                     }
                     
-                    return Err(TqError::TableSchemaInconsistent {
+                    errors.push((path::join_index(path, idx), TqError::TableSchemaInconsistent {
                         name: table.name.clone(),
-                        message,
-                    });
+                        message: message.trim_end().to_string(),
+                    }));
                 }
             }
         }
         
+        Self::collect_column_type_errors(table, path, &options.column_types, errors);
+        
         // Recursively validate values in rows
-        for row in &table.rows {
-            for value in row.values() {
-                Self::validate(value)?;
+        for (idx, row) in table.rows.iter().enumerate() {
+            let row_path = path::join_index(path, idx);
+            let mut keys: Vec<&String> = row.keys().collect();
+            keys.sort();
+            for key in keys {
+                Self::collect_errors(&row[key], &path::join_key(&row_path, key), options, errors);
             }
         }
-        
-        Ok(())
     }
     
-    pub fn check_structure(value: &Value) -> Vec<String> {
-        let mut issues = Vec::new();
-        Self::check_structure_recursive(value, "", &mut issues);
-        issues
+    /// Checks that every column holds a single type. The expected type is the
+    /// most common non-null type in the column, ties going to the earliest row.
+    pub fn validate_column_types(table: &Table, policy: &ColumnTypePolicy) -> Result<()> {
+        let mut errors = Vec::new();
+        Self::collect_column_type_errors(table, "", policy, &mut errors);
+        match errors.into_iter().next() {
+            Some((_, error)) => Err(error),
+            None => Ok(()),
+        }
     }
     
-    fn check_structure_recursive(value: &Value, path: &str, issues: &mut Vec<String>) {
-        match value {
-            Value::Object(obj) => {
-                if obj.is_empty() {
-                    let prefix = if path.is_empty() { 
-                        String::new() 
-                    } else { 
-                        format!("{}: ", path) 
-                    };
-                    issues.push(format!("{}Empty object", prefix));
-                }
-                for (key, val) in obj {
-                    let new_path = if path.is_empty() {
-                        key.clone()
-                    } else {
-                        format!("{}.{}", path, key)
-                    };
-                    Self::check_structure_recursive(val, &new_path, issues);
+    fn collect_column_type_errors(
+        table: &Table,
+        path: &str,
+        policy: &ColumnTypePolicy,
+        errors: &mut Vec<(String, TqError)>,
+    ) {
+        let mut columns: Vec<&String> = table.rows.iter().flat_map(|row| row.keys()).collect();
+        columns.sort();
+        columns.dedup();
+        
+        for column in columns {
+            let cells: Vec<(usize, &Value)> = table
+                .rows
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, row)| row.get(column).map(|v| (idx, v)))
+                .collect();
+            
+            let Some(expected) = Self::infer_column_type(cells.iter().map(|(_, v)| *v), policy) else {
+                continue;
+            };
+            
+            for (idx, cell) in cells {
+                let found = Self::column_type_of(cell, policy);
+                let allowed = found == expected || (found == "null" && policy.allow_null);
+                if !allowed {
+                    let cell_path = path::join_key(&path::join_index(path, idx), column);
+                    errors.push((cell_path, TqError::TableColumnTypeMismatch {
+                        table: table.name.clone(),
+                        column: column.clone(),
+                        row: idx + 1,
+                        expected: expected.to_string(),
+                        found: found.to_string(),
+                    }));
                 }
             }
-            Value::Array(arr) => {
-                if arr.is_empty() {
-                    issues.push(format!("{}: Empty array", path));
-                }
-                for (idx, val) in arr.iter().enumerate() {
-                    let new_path = format!("{}[{}]", path, idx);
-                    Self::check_structure_recursive(val, &new_path, issues);
-                }
+        }
+    }
+    
+    fn infer_column_type<'a>(
+        cells: impl Iterator<Item = &'a Value>,
+        policy: &ColumnTypePolicy,
+    ) -> Option<&'static str> {
+        // (type, count, first seen)
+        let mut counts: Vec<(&'static str, usize, usize)> = Vec::new();
+        for (idx, cell) in cells.enumerate() {
+            let cell_type = Self::column_type_of(cell, policy);
+            if cell_type == "null" {
+                continue;
             }
-            Value::Table(table) => {
-                if table.rows.is_empty() && table.declared_rows > 0 {
-                    issues.push(format!("{}: Table declared with {} rows but is empty", path, table.declared_rows));
-                }
+            match counts.iter_mut().find(|(t, _, _)| *t == cell_type) {
+                Some(entry) => entry.1 += 1,
+                None => counts.push((cell_type, 1, idx)),
             }
-            _ => {}
         }
+        
+        counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.2.cmp(&a.2)))
+            .map(|(t, _, _)| t)
+    }
+    
+    fn column_type_of(value: &Value, policy: &ColumnTypePolicy) -> &'static str {
+        match value {
+            Value::String(s) if policy.numeric_strings_as_numbers && s.trim().parse::<f64>().is_ok() => "number",
+            other => other.type_name(),
+        }
+    }
+    
+    /// Adds the lines of surplus rows to row-count diagnostics, which the
+    /// value alone doesn't record.
+    pub fn locate_surplus_rows(diagnostics: &mut [Diagnostic], value: &Value, source_map: &SourceMap) {
+        for diagnostic in diagnostics.iter_mut().filter(|d| d.code == codes::TABLE_ROW_MISMATCH) {
+            let Some(table) = value.get_path(&diagnostic.path).and_then(Value::as_table) else {
+                continue;
+            };
+            let extra_lines: Vec<usize> = (table.declared_rows..table.rows.len())
+                .filter_map(|idx| source_map.get(&path::join_index(&diagnostic.path, idx)))
+                .map(|span| span.line)
+                .collect();
+            if extra_lines.is_empty() {
+                continue;
+            }
+            diagnostic.message = TqError::TableRowMismatch {
+                name: table.name.clone(),
+                declared: table.declared_rows,
+                actual: table.rows.len(),
+                extra_lines,
+            }
+            .to_string();
+        }
+    }
+    
+    /// Reports structural oddities such as empty containers, using the default rules.
+    pub fn check_structure(value: &Value) -> Vec<Diagnostic> {
+        RuleRegistry::with_defaults().run(value)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostic::{codes, Severity};
     use crate::value::Table;
+    use std::collections::HashMap;
 
     #[test]
     fn test_validate_primitives() {
@@ -224,17 +539,116 @@ mod tests {
         }
     }
 
+    fn typed_table(ids: Vec<Value>) -> Table {
+        Table {
+            name: "items".to_string(),
+            declared_rows: ids.len(),
+            rows: ids
+                .into_iter()
+                .map(|id| {
+                    let mut row = HashMap::new();
+                    row.insert("id".to_string(), id);
+                    row
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_column_type_mismatch() {
+        let table = typed_table(vec![
+            Value::Number(1.0),
+            Value::String("abc".to_string()),
+            Value::Number(3.0),
+        ]);
+        
+        let result = Validator::validate(&Value::Table(table));
+        match result {
+            Err(TqError::TableColumnTypeMismatch { table, column, row, expected, found }) => {
+                assert_eq!(table, "items");
+                assert_eq!(column, "id");
+                assert_eq!(row, 2);
+                assert_eq!(expected, "number");
+                assert_eq!(found, "string");
+            }
+            other => panic!("Expected column type mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_column_type_policy() {
+        let table = Value::Table(typed_table(vec![
+            Value::Number(1.0),
+            Value::String("2".to_string()),
+            Value::Null,
+        ]));
+        
+        assert!(Validator::validate(&table).is_err());
+        
+        let lenient = ColumnTypePolicy {
+            numeric_strings_as_numbers: true,
+            allow_null: true,
+        };
+        assert!(Validator::validate_with_policy(&table, &lenient).is_ok());
+        
+        let no_nulls = ColumnTypePolicy {
+            numeric_strings_as_numbers: true,
+            allow_null: false,
+        };
+        let result = Validator::validate_with_policy(&table, &no_nulls);
+        assert!(matches!(result, Err(TqError::TableColumnTypeMismatch { row: 3, .. })));
+    }
+
+    #[test]
+    fn test_validate_all_collects_every_problem() {
+        let mut row1 = HashMap::new();
+        row1.insert("id".to_string(), Value::Number(1.0));
+        row1.insert("name".to_string(), Value::String("Alice".to_string()));
+        
+        let mut row2 = HashMap::new();
+        row2.insert("id".to_string(), Value::String("two".to_string()));
+        
+        let mut row3 = HashMap::new();
+        row3.insert("id".to_string(), Value::Number(3.0));
+        row3.insert("email".to_string(), Value::String("c@example.com".to_string()));
+        
+        let table = Table {
+            name: "users".to_string(),
+            declared_rows: 4,
+            rows: vec![row1, row2, row3],
+        };
+        
+        let mut obj = HashMap::new();
+        obj.insert("users".to_string(), Value::Table(table));
+        obj.insert("tags".to_string(), Value::Array(vec![]));
+        
+        let diagnostics = Validator::validate_all(&Value::Object(obj));
+        let summary: Vec<(&str, &str, Severity)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.path.as_str(), d.severity))
+            .collect();
+        
+        assert_eq!(summary, vec![
+            (codes::TABLE_ROW_MISMATCH, "users", Severity::Error),
+            (codes::TABLE_SCHEMA_INCONSISTENT, "users[1]", Severity::Error),
+            (codes::TABLE_SCHEMA_INCONSISTENT, "users[2]", Severity::Error),
+            (codes::TABLE_COLUMN_TYPE_MISMATCH, "users[1].id", Severity::Error),
+            (codes::EMPTY_ARRAY, "tags", Severity::Warning),
+        ]);
+        assert!(diagnostics[2].message.contains("Missing fields: [\"name\"]. Extra fields: [\"email\"]."));
+    }
+
     #[test]
     fn test_check_structure_empty() {
         let obj = Value::Object(HashMap::new());
         let issues = Validator::check_structure(&obj);
         assert_eq!(issues.len(), 1);
-        assert!(issues[0].contains("Empty object"));
+        assert!(issues[0].message.contains("Empty object"));
         
         let arr = Value::Array(vec![]);
         let issues = Validator::check_structure(&arr);
         assert_eq!(issues.len(), 1);
-        assert!(issues[0].contains("Empty array"));
+        assert!(issues[0].message.contains("Empty array"));
     }
 
     #[test]
@@ -249,8 +663,9 @@ mod tests {
         let issues = Validator::check_structure(&value);
         
         assert_eq!(issues.len(), 1);
-        assert!(issues[0].contains("nested.empty"));
-        assert!(issues[0].contains("Empty array"));
+        assert_eq!(issues[0].path, "nested.empty");
+        assert_eq!(issues[0].code, codes::EMPTY_ARRAY);
+        assert!(issues[0].message.contains("Empty array"));
     }
 
     #[test]
@@ -268,6 +683,64 @@ mod tests {
         let issues = Validator::check_structure(&value);
         
         assert_eq!(issues.len(), 1);
-        assert!(issues[0].contains("declared with 5 rows but is empty"));
+        assert!(issues[0].message.contains("declared with 5 rows but is empty"));
+    }
+
+    #[test]
+    fn test_locate_surplus_rows() {
+        use crate::toon_parser::ToonParser;
+        
+        let input = "users[1]:\n  - id: 1\n  - id: 2\n  - id: 3";
+        let (value, source_map) = ToonParser::parse_with_source_map(input).unwrap();
+        let mut diagnostics = Validator::validate_all(&value);
+        Validator::locate_surplus_rows(&mut diagnostics, &value, &source_map);
+        
+        assert_eq!(diagnostics[0].code, codes::TABLE_ROW_MISMATCH);
+        assert_eq!(diagnostics[0].message, "Table users declared with 1 rows but found 3; extra rows at lines 3, 4");
+    }
+
+    #[test]
+    fn test_builder_profiles() {
+        use crate::toon_parser::ToonParser;
+        
+        let value = ToonParser::parse(r#"users[3]:
+  - id: 1
+    name: "Alice"
+  - id: 1
+  - id: "3"
+    name: "Carol""#).unwrap();
+        let found = |validator: Validator| -> Vec<(String, Severity)> {
+            validator.check(&value).into_iter().map(|d| (d.code, d.severity)).collect()
+        };
+        
+        let default = found(Validator::builder().build().unwrap());
+        assert!(default.contains(&(codes::TABLE_SCHEMA_INCONSISTENT.to_string(), Severity::Error)));
+        assert!(default.contains(&(codes::DUPLICATE_KEY.to_string(), Severity::Warning)));
+        
+        // unique-keys reports the repeated id
+        let strict = found(Validator::builder().profile(ValidationProfile::Strict).build().unwrap());
+        assert!(strict.contains(&(codes::DUPLICATE_KEY.to_string(), Severity::Error)));
+        
+        let empty = ToonParser::parse("users[0]:").unwrap();
+        let empty_table = |profile| Validator::builder().profile(profile).build().unwrap().check(&empty);
+        assert_eq!(empty_table(ValidationProfile::Default)[0].severity, Severity::Warning);
+        assert_eq!(empty_table(ValidationProfile::Strict)[0].severity, Severity::Error);
+        assert_eq!(empty_table(ValidationProfile::Strict)[0].code, codes::EMPTY_TABLE);
+        
+        // Missing columns and the numeric string are accepted
+        let lenient = found(Validator::builder().profile(ValidationProfile::Lenient).build().unwrap());
+        assert_eq!(lenient, vec![(codes::DUPLICATE_KEY.to_string(), Severity::Warning)]);
+        
+        let overridden = found(
+            Validator::builder()
+                .profile(ValidationProfile::Lenient)
+                .rule_level("unique-keys", RuleLevel::Off)
+                .build()
+                .unwrap(),
+        );
+        assert!(overridden.is_empty());
+        
+        assert!(Validator::builder().rule_level("no-such-rule", RuleLevel::Error).build().is_err());
+        assert_eq!("Strict".parse::<ValidationProfile>().unwrap(), ValidationProfile::Strict);
     }
-}
\ No newline at end of file
+}
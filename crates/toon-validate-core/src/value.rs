@@ -20,6 +20,66 @@ pub struct Table {
     pub rows: Vec<HashMap<String, Value>>,
 }
 
+/// A row that repeats an earlier row's values in a set of key columns.
+/// Row indices are 0-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateRow {
+    pub first: usize,
+    pub duplicate: usize,
+    pub key: Vec<Value>,
+}
+
+impl DuplicateRow {
+    /// Describes the duplicate with 1-based row numbers, e.g.
+    /// `duplicate id 7 in rows 2 and 5`.
+    pub fn message(&self, columns: &[String]) -> String {
+        let values: Vec<String> = self.key.iter().map(|v| v.to_json().to_string()).collect();
+        let (columns, values) = if columns.len() == 1 {
+            (columns[0].clone(), values[0].clone())
+        } else {
+            (format!("({})", columns.join(", ")), format!("({})", values.join(", ")))
+        };
+        format!(
+            "duplicate {} {} in rows {} and {}",
+            columns,
+            values,
+            self.first + 1,
+            self.duplicate + 1
+        )
+    }
+}
+
+/// Finds rows sharing the same values in all of `columns`, each reported
+/// against the first row with that key. `None` rows (e.g. array items that
+/// aren't objects) and rows lacking a key column are skipped, as are keys
+/// holding `null`: like SQL unique constraints, nulls never collide.
+pub fn find_duplicate_rows<'a>(
+    rows: impl IntoIterator<Item = Option<&'a HashMap<String, Value>>>,
+    columns: &[String],
+) -> Vec<DuplicateRow> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut duplicates = Vec::new();
+    
+    for (idx, row) in rows.into_iter().enumerate() {
+        let Some(key) = row.and_then(|row| columns.iter().map(|c| row.get(c).cloned()).collect::<Option<Vec<Value>>>()) else {
+            continue;
+        };
+        if key.contains(&Value::Null) {
+            continue;
+        }
+        // Values aren't hashable (floats), so key on their JSON form
+        let fingerprint = serde_json::Value::Array(key.iter().map(Value::to_json).collect()).to_string();
+        match seen.get(&fingerprint) {
+            Some(&first) => duplicates.push(DuplicateRow { first, duplicate: idx, key }),
+            None => {
+                seen.insert(fingerprint, idx);
+            }
+        }
+    }
+    
+    duplicates
+}
+
 impl Value {
     pub fn is_table(&self) -> bool {
         matches!(self, Value::Table(_))
@@ -38,4 +98,132 @@ impl Value {
             _ => None,
         }
     }
-}
\ No newline at end of file
+    
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+            Value::Table(_) => "table",
+        }
+    }
+    
+    /// Converts to JSON, representing tables as arrays of row objects.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Value::Null => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+                serde_json::Value::from(*n as i64)
+            }
+            Value::Number(n) => serde_json::Number::from_f64(*n)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Value::String(s) => serde_json::Value::String(s.clone()),
+            Value::Array(arr) => serde_json::Value::Array(arr.iter().map(Value::to_json).collect()),
+            Value::Object(obj) => serde_json::Value::Object(
+                obj.iter().map(|(k, v)| (k.clone(), v.to_json())).collect(),
+            ),
+            Value::Table(table) => serde_json::Value::Array(
+                table
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        serde_json::Value::Object(
+                            row.iter().map(|(k, v)| (k.clone(), v.to_json())).collect(),
+                        )
+                    })
+                    .collect(),
+            ),
+        }
+    }
+    
+    /// The rows of a table, or the items of an array as objects (`None` for
+    /// items that aren't objects). `None` for any other value.
+    pub fn object_rows(&self) -> Option<Vec<Option<&HashMap<String, Value>>>> {
+        match self {
+            Value::Table(table) => Some(table.rows.iter().map(Some).collect()),
+            Value::Array(arr) => Some(arr.iter().map(Value::as_object).collect()),
+            _ => None,
+        }
+    }
+    
+    /// Looks up a nested value by a path such as `data.users` or `items[2].tags`.
+    /// An empty path refers to the value itself.
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        let mut current = self;
+        
+        for segment in path.split('.').filter(|s| !s.is_empty()) {
+            let (key, indices) = match segment.find('[') {
+                Some(pos) => (&segment[..pos], &segment[pos..]),
+                None => (segment, ""),
+            };
+            
+            if !key.is_empty() {
+                current = current.as_object()?.get(key)?;
+            }
+            
+            for index in indices.split(']').filter(|s| !s.is_empty()) {
+                let index: usize = index.strip_prefix('[')?.parse().ok()?;
+                current = match current {
+                    Value::Array(arr) => arr.get(index)?,
+                    _ => return None,
+                };
+            }
+        }
+        
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_path() {
+        let mut inner = HashMap::new();
+        inner.insert("tags".to_string(), Value::Array(vec![
+            Value::String("a".to_string()),
+            Value::String("b".to_string()),
+        ]));
+        
+        let mut obj = HashMap::new();
+        obj.insert("data".to_string(), Value::Object(inner));
+        let value = Value::Object(obj);
+        
+        assert_eq!(value.get_path(""), Some(&value));
+        assert_eq!(value.get_path("data.tags[1]"), Some(&Value::String("b".to_string())));
+        assert_eq!(value.get_path("data.tags[5]"), None);
+        assert_eq!(value.get_path("data.missing"), None);
+    }
+
+    #[test]
+    fn test_find_duplicate_rows() {
+        let row = |id: f64, line: f64| {
+            let mut row = HashMap::new();
+            row.insert("id".to_string(), Value::Number(id));
+            row.insert("line".to_string(), Value::Number(line));
+            row
+        };
+        let rows = [row(1.0, 1.0), row(2.0, 1.0), row(1.0, 2.0), row(1.0, 1.0)];
+        
+        let by_id = find_duplicate_rows(rows.iter().map(Some), &["id".to_string()]);
+        assert_eq!(by_id.iter().map(|d| (d.first, d.duplicate)).collect::<Vec<_>>(), vec![(0, 2), (0, 3)]);
+        assert_eq!(by_id[0].message(&["id".to_string()]), "duplicate id 1 in rows 1 and 3");
+        
+        let composite = ["id".to_string(), "line".to_string()];
+        let by_both = find_duplicate_rows(rows.iter().map(Some), &composite);
+        assert_eq!(by_both.len(), 1);
+        assert_eq!(by_both[0].message(&composite), "duplicate (id, line) (1, 1) in rows 1 and 4");
+        
+        let mut null_id = row(1.0, 1.0);
+        null_id.insert("id".to_string(), Value::Null);
+        let rows = [null_id.clone(), null_id];
+        assert!(find_duplicate_rows(rows.iter().map(Some), &["id".to_string()]).is_empty());
+        assert!(find_duplicate_rows(rows.iter().map(Some), &composite).is_empty());
+    }
+}